@@ -1,4 +1,5 @@
 use crate::{Node, RootedTree};
+use std::collections::HashSet;
 use std::hash::Hash;
 
 impl<I: Eq + PartialEq + Clone + Hash, N: Node<I> + Clone> RootedTree<I, N> {
@@ -33,6 +34,72 @@ impl<I: Eq + PartialEq + Clone + Hash, N: Node<I> + Clone> RootedTree<I, N> {
 
         Some(sub_tree)
     }
+
+    /// Like `clone_from_with_lvl`, also returns the ids just past the level boundary.
+    pub fn clone_to_depth(&self, id: I, lvl: u32) -> (RootedTree<I, N>, Vec<I>) {
+        let kept_ids: HashSet<I> = self
+            .list_child_ids_with_lvl(&id, Some(lvl))
+            .into_iter()
+            .collect();
+        let cut_ids = self
+            .list_child_ids_with_lvl(&id, Some(lvl.saturating_add(1)))
+            .into_iter()
+            .filter(|child_id| !kept_ids.contains(child_id))
+            .collect();
+        let sub_tree = self
+            .clone_from_with_lvl(id, Some(lvl))
+            .unwrap_or_else(RootedTree::new);
+        (sub_tree, cut_ids)
+    }
+
+    /// Non-mutating `prune_to_depth_returning`: the tree cut below `lvl` and the subtrees rooted
+    /// at depth `lvl + 1`.
+    pub fn cut_at_depth(&self, lvl: u32) -> (RootedTree<I, N>, Vec<RootedTree<I, N>>) {
+        let mut top_tree = self.clone();
+        let forest = top_tree.prune_to_depth_returning(lvl);
+        (top_tree, forest)
+    }
+
+    /// Clone the subtree of `id` with every id remapped by `f`, nodes built with
+    /// `new_node(node, new_id)`. The parent id of the subtree root is kept as is, it still refers
+    /// to the node outside of the clone.
+    pub fn clone_with_new_ids<F, G>(&self, id: I, f: F, new_node: G) -> Option<RootedTree<I, N>>
+    where
+        F: Fn(&I) -> I,
+        G: Fn(&N, I) -> N,
+    {
+        self.get_node(&id)?;
+        let sub_tree = self.clone_from(id)?;
+        let remap = |node: &N, parent_id: Option<I>| {
+            let mut new_node = new_node(node, f(&node.id()));
+            match parent_id {
+                Some(parent_id) => new_node.set_parent_id(parent_id),
+                None => new_node.remove_parent_id(),
+            }
+            new_node.set_child_ids(node.child_ids_vec().iter().map(&f).collect());
+            new_node
+        };
+
+        let mut new_tree = RootedTree::new();
+        new_tree.root_node = sub_tree
+            .root_node
+            .as_ref()
+            .map(|node| remap(node, node.parent_id()));
+        for node in sub_tree.child_nodes.values() {
+            let node = remap(node, node.parent_id().map(|parent_id| f(&parent_id)));
+            new_tree.child_nodes.insert(node.id(), node);
+        }
+        Some(new_tree)
+    }
+
+    /// Clone the nodes in level-order, root first, so that the result rebuilds the tree through
+    /// `TryFrom<Vec<N>>`.
+    pub fn flatten(&self) -> Vec<N> {
+        match &self.root_node {
+            Some(root_node) => self.bfs_from(&root_node.id()).cloned().collect(),
+            None => vec![],
+        }
+    }
 }
 
 impl<I: Eq + PartialEq + Clone + Hash, N: Node<I> + Clone> Clone for RootedTree<I, N> {
@@ -60,6 +127,30 @@ mod tests {
         assert!(tree == cloned_tree);
     }
 
+    #[test]
+    fn flatten() {
+        let mut tree = RootedTree::<i32, DataNode>::new();
+        assert!(tree.flatten().is_empty());
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        let mut node = DataNode::new(4);
+        node.add_child_id(5);
+        tree.add_node(Some(1), node).unwrap();
+
+        let nodes = tree.flatten();
+        assert_eq!(
+            nodes.iter().map(|node| node.id()).collect::<Vec<_>>(),
+            vec![1, 2, 4, 3]
+        );
+        let rebuilt_tree: RootedTree<i32, DataNode> = nodes.try_into().unwrap();
+        assert!(rebuilt_tree == tree);
+
+        let sub_tree = tree.clone_from(2).unwrap();
+        let rebuilt_sub_tree: RootedTree<i32, DataNode> = sub_tree.flatten().try_into().unwrap();
+        assert!(rebuilt_sub_tree == sub_tree);
+    }
+
     #[test]
     fn clone_root() {
         let mut tree = RootedTree::new();
@@ -87,7 +178,10 @@ mod tests {
         assert_eq!(sub_tree.get_node(&2).unwrap().parent_id(), Some(1));
         assert_eq!(sub_tree.get_node(&2).unwrap().child_ids_vec(), vec![3]);
         assert_eq!(sub_tree.get_node(&3).unwrap().parent_id(), Some(2));
-        assert_eq!(sub_tree.get_node(&3).unwrap().child_ids_vec(), vec![]);
+        assert_eq!(
+            sub_tree.get_node(&3).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
     }
 
     #[test]
@@ -106,7 +200,10 @@ mod tests {
 
         assert_eq!(sub_tree.len(), 1);
         assert_eq!(sub_tree.get_node(&3).unwrap().parent_id(), Some(2));
-        assert_eq!(sub_tree.get_node(&3).unwrap().child_ids_vec(), vec![]);
+        assert_eq!(
+            sub_tree.get_node(&3).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
     }
 
     #[test]
@@ -140,7 +237,10 @@ mod tests {
         assert_eq!(sub_tree.get_node(&2).unwrap().parent_id(), Some(1));
         assert_eq!(sub_tree.get_node(&2).unwrap().child_ids_vec(), vec![3]);
         assert_eq!(sub_tree.get_node(&3).unwrap().parent_id(), Some(2));
-        assert_eq!(sub_tree.get_node(&3).unwrap().child_ids_vec(), vec![]);
+        assert_eq!(
+            sub_tree.get_node(&3).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
     }
 
     #[test]
@@ -155,6 +255,113 @@ mod tests {
         assert!(tree != sub_tree);
         assert_eq!(sub_tree.len(), 1);
         assert_eq!(sub_tree.get_node(&3).unwrap().parent_id(), Some(2));
-        assert_eq!(sub_tree.get_node(&3).unwrap().child_ids_vec(), vec![]);
+        assert_eq!(
+            sub_tree.get_node(&3).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
+    }
+
+    #[test]
+    fn clone_to_depth() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(2), DataNode::new(5)).unwrap();
+        tree.add_node(Some(3), DataNode::new(6)).unwrap();
+        tree.add_node(Some(6), DataNode::new(7)).unwrap();
+
+        let (sub_tree, cut_ids) = tree.clone_to_depth(1, 1);
+
+        assert_eq!(sub_tree.len(), 3);
+        assert_eq!(cut_ids, vec![4, 5, 6]);
+
+        let (sub_tree, cut_ids) = tree.clone_to_depth(3, 1);
+
+        assert_eq!(sub_tree.len(), 2);
+        assert_eq!(cut_ids, vec![7]);
+
+        let (sub_tree, cut_ids) = tree.clone_to_depth(1, 3);
+
+        assert!(sub_tree == tree);
+        assert_eq!(cut_ids, Vec::<i32>::new());
+
+        let (sub_tree, cut_ids) = tree.clone_to_depth(1, u32::MAX);
+
+        assert!(sub_tree == tree);
+        assert_eq!(cut_ids, Vec::<i32>::new());
+    }
+
+    #[test]
+    fn cut_at_depth() {
+        let mut tree = RootedTree::<i32, DataNode>::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(3), DataNode::new(5)).unwrap();
+        tree.add_node(Some(4), DataNode::new(6)).unwrap();
+        tree.add_node(Some(4), DataNode::new(7)).unwrap();
+        tree.add_node(Some(5), DataNode::new(8)).unwrap();
+        tree.add_node(Some(8), DataNode::new(9)).unwrap();
+
+        let (top_tree, forest) = tree.cut_at_depth(2);
+
+        assert_eq!(tree.len(), 9);
+        assert_eq!(top_tree.len(), 5);
+        assert_eq!(forest.iter().map(|t| t.len()).sum::<usize>(), 4);
+
+        let mut forest_roots: Vec<(i32, Option<i32>)> = forest
+            .iter()
+            .map(|sub_tree| {
+                let root_node = sub_tree.root_node.as_ref().unwrap();
+                (root_node.id(), root_node.parent_id())
+            })
+            .collect();
+        forest_roots.sort();
+        assert_eq!(forest_roots, vec![(6, Some(4)), (7, Some(4)), (8, Some(5))]);
+        assert_eq!(
+            top_tree.get_node(&4).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
+        assert_eq!(
+            top_tree.get_node(&5).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
+
+        let (top_tree, forest) = tree.cut_at_depth(u32::MAX);
+        assert!(top_tree == tree);
+        assert!(forest.is_empty());
+    }
+
+    #[test]
+    fn clone_with_new_ids() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+
+        tree.get_mut_node(&3).unwrap().value = 30;
+
+        let new_node = |node: &DataNode, id| DataNode { id, ..node.clone() };
+        let sub_tree = tree.clone_with_new_ids(2, |id| id + 100, new_node).unwrap();
+
+        assert_eq!(tree.len(), 4);
+        assert_eq!(sub_tree.len(), 3);
+        assert_eq!(sub_tree.get_node(&102).unwrap().parent_id(), Some(1));
+        assert_eq!(
+            sub_tree.get_node(&102).unwrap().child_ids_vec(),
+            vec![103, 104]
+        );
+        assert_eq!(sub_tree.get_node(&103).unwrap().parent_id(), Some(102));
+        assert_eq!(sub_tree.get_node(&104).unwrap().parent_id(), Some(102));
+        assert_eq!(sub_tree.get_node(&103).unwrap().value, 30);
+        assert!(sub_tree.get_node(&2).is_none());
+
+        assert!(tree
+            .clone_with_new_ids(5, |id| id + 100, new_node)
+            .is_none());
     }
 }
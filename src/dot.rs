@@ -0,0 +1,106 @@
+use crate::{Node, RootedTree};
+use std::fmt::Display;
+use std::hash::Hash;
+
+impl<I: Eq + PartialEq + Clone + Hash + Display, N: Node<I>> RootedTree<I, N> {
+    /// Graphviz `digraph` of the tree, nodes labeled by their id. Missing child nodes are drawn
+    /// as dashed placeholders reached by dashed edges.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph {\n");
+        if let Some(root_node) = &self.root_node {
+            for node in std::iter::once(root_node).chain(self.descendants(&root_node.id())) {
+                let id = node.id();
+                out.push_str(&format!(
+                    "    {} [label={}];\n",
+                    dot_id(&id),
+                    dot_label(&id)
+                ));
+                for child_id in node.child_ids_vec() {
+                    if self.child_nodes.contains_key(&child_id) {
+                        out.push_str(&format!("    {} -> {};\n", dot_id(&id), dot_id(&child_id)));
+                    } else {
+                        out.push_str(&format!(
+                            "    {} [label={}, style=dashed];\n",
+                            dot_id(&child_id),
+                            dot_label(&child_id)
+                        ));
+                        out.push_str(&format!(
+                            "    {} -> {} [style=dashed];\n",
+                            dot_id(&id),
+                            dot_id(&child_id)
+                        ));
+                    }
+                }
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+// Node identifier, quoted unless it is made of plain identifier characters
+fn dot_id<I: Display>(id: &I) -> String {
+    let id = format!("n{}", id);
+    if id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        id
+    } else {
+        dot_label(&id)
+    }
+}
+
+fn dot_label<I: Display>(id: &I) -> String {
+    format!(
+        "\"{}\"",
+        id.to_string().replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::*;
+
+    #[test]
+    fn to_dot() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+
+        assert_eq!(
+            tree.to_dot(),
+            r#"digraph {
+    n1 [label="1"];
+    n1 -> n2;
+    n1 -> n3;
+    n2 [label="2"];
+    n2 -> n4;
+    n4 [label="4"];
+    n3 [label="3"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn to_dot_partial_children() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        let mut node = DataNode::new(2);
+        node.add_child_id(3);
+        tree.add_node(Some(1), node).unwrap();
+
+        let dot = tree.to_dot();
+        assert!(dot.contains("    n1 -> n2;\n"));
+        assert!(dot.contains("    n3 [label=\"3\", style=dashed];\n"));
+        assert!(dot.contains("    n2 -> n3 [style=dashed];\n"));
+    }
+
+    #[test]
+    fn to_dot_path_ids() {
+        let dot = path_tree().to_dot();
+        assert!(dot.contains("    \"n/\" -> \"n/home\";\n"));
+        assert!(dot.contains("    \"n/home\" [label=\"/home\"];\n"));
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{Node, RootedTree};
+use std::fmt::Display;
+use std::hash::Hash;
+
+impl<I: Eq + PartialEq + Clone + Hash, N: Node<I>> RootedTree<I, N> {
+    /// Nodes whose id matches `pred`, in level-order.
+    pub fn filter_ids<F: Fn(&I) -> bool>(&self, pred: F) -> Vec<&N> {
+        if let Some(root_node) = &self.root_node {
+            self.bfs_from(&root_node.id())
+                .filter(|node| pred(&node.id()))
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+
+    /// Number of nodes of the tree, root included, matching `pred`.
+    pub fn count_where<F: Fn(&N) -> bool>(&self, pred: F) -> usize {
+        self.root_node
+            .iter()
+            .chain(self.child_nodes.values())
+            .filter(|node| pred(node))
+            .count()
+    }
+}
+
+impl<I: Eq + PartialEq + Clone + Hash + Display, N: Node<I>> RootedTree<I, N> {
+    /// Nodes whose displayed id contains `needle`, in level-order.
+    pub fn find_ids_containing(&self, needle: &str) -> Vec<&N> {
+        self.filter_ids(|id| id.to_string().contains(needle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_data::*;
+    use crate::*;
+
+    #[test]
+    fn filter_ids() {
+        let tree = path_tree();
+
+        let ids: Vec<String> = tree
+            .filter_ids(|id| id.starts_with("/home"))
+            .into_iter()
+            .map(|node| node.id())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "/home",
+                "/home/alice",
+                "/home/bob",
+                "/home/alice/Documents",
+                "/home/bob/Documents"
+            ]
+        );
+    }
+
+    #[test]
+    fn count_where() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(2), DataNode::new(5)).unwrap();
+
+        assert_eq!(tree.count_where(|node| node.id() % 2 == 1), 3);
+        assert_eq!(tree.count_where(|node| node.id() > 3), 2);
+        assert_eq!(tree.count_where(|_| true), tree.len());
+    }
+
+    #[test]
+    fn find_ids_containing() {
+        let tree = path_tree();
+
+        let ids: Vec<String> = tree
+            .find_ids_containing("Documents")
+            .into_iter()
+            .map(|node| node.id())
+            .collect();
+        assert_eq!(ids, vec!["/home/alice/Documents", "/home/bob/Documents"]);
+
+        assert!(tree.find_ids_containing("Downloads").is_empty());
+    }
+}
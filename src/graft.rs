@@ -0,0 +1,362 @@
+use crate::{Error, Node, Result, RootedTree};
+use std::hash::Hash;
+
+impl<I: Eq + PartialEq + Clone + Hash, N: Node<I>> RootedTree<I, N> {
+    /// Move the subtree of `id` under `dest_parent` in `dest`.
+    ///
+    /// Everything is validated before any mutation, so both trees are left untouched on failure.
+    pub fn move_subtree_to(
+        &mut self,
+        id: &I,
+        dest: &mut RootedTree<I, N>,
+        dest_parent: &I,
+    ) -> Result<()> {
+        if self.get_node(id).is_none() {
+            return Err(Error::NodeDoesNotExist);
+        }
+        if dest.get_node(dest_parent).is_none() {
+            return Err(Error::ParentNodeDoesNotExist);
+        }
+        let mut ids = self.list_child_ids(id);
+        ids.push(id.clone());
+        // Missing child ids of a partial subtree count too, `dest` would list them twice
+        if ids.iter().any(|id| dest.get_node(id).is_some()) {
+            return Err(Error::NodeAlreadyExists);
+        }
+
+        self.detach_from_parent(id);
+        if let Some(sub_tree) = self.take(id.clone()) {
+            dest.attach_sub_tree(dest_parent, sub_tree);
+        }
+        Ok(())
+    }
+
+    /// Insert all the nodes of `sub_tree` under `parent_id`, its root becoming a child of
+    /// `parent_id`.
+    ///
+    /// Fails with `NodeAlreadyExists` if any id of `sub_tree` is already in the tree.
+    pub fn graft(&mut self, parent_id: &I, sub_tree: RootedTree<I, N>) -> Result<()> {
+        if self.get_node(parent_id).is_none() {
+            return Err(Error::ParentNodeDoesNotExist);
+        }
+        if sub_tree
+            .root_node
+            .iter()
+            .map(|node| node.id())
+            .chain(sub_tree.child_nodes.keys().cloned())
+            .any(|id| self.get_node(&id).is_some())
+        {
+            return Err(Error::NodeAlreadyExists);
+        }
+        self.attach_sub_tree(parent_id, sub_tree);
+        Ok(())
+    }
+
+    /// Merge the nodes of `other` into the tree, level by level.
+    ///
+    /// Nodes present in both trees are replaced by `resolve(node, other_node)` and keep their
+    /// place in the tree, gaining the children that only exist in `other`. The root of `other`
+    /// must be in the tree, or have its parent in the tree.
+    ///
+    /// Fails with `NodeAlreadyExists` if a node of `other` has a child that is already in the
+    /// tree under another parent. Nothing is merged on failure.
+    pub fn merge_with<F: Fn(&N, &N) -> N>(
+        &mut self,
+        mut other: RootedTree<I, N>,
+        resolve: F,
+    ) -> Result<()> {
+        let ids: Vec<I> = if let Some(root_node) = &other.root_node {
+            other
+                .bfs_from(&root_node.id())
+                .map(|node| node.id())
+                .collect()
+        } else {
+            return Ok(());
+        };
+        if self.root_node.is_none() {
+            *self = other;
+            return Ok(());
+        }
+        if let Some(root_node) = &other.root_node {
+            let parent_in_tree = match root_node.parent_id() {
+                Some(parent_id) => self.get_node(&parent_id).is_some(),
+                None => false,
+            };
+            if self.get_node(&root_node.id()).is_none() && !parent_in_tree {
+                return Err(Error::NodeDoesNotExist);
+            }
+        }
+        for id in &ids {
+            let other_node = match other.get_node(id) {
+                Some(node) => node,
+                None => continue,
+            };
+            for child_id in other_node.child_ids_vec() {
+                if let Some(node) = self.get_node(&child_id) {
+                    if node.parent_id().as_ref() != Some(id) {
+                        return Err(Error::NodeAlreadyExists);
+                    }
+                }
+            }
+        }
+
+        let mut other_root_node = other.root_node.take();
+        for id in ids {
+            let other_node = if let Some(node) = other_root_node.take() {
+                node
+            } else if let Some(node) = other.child_nodes.remove(&id) {
+                node
+            } else {
+                continue;
+            };
+            // Children already in the tree are already children of this node
+            let new_child_ids: Vec<I> = other_node
+                .child_ids_vec()
+                .into_iter()
+                .filter(|child_id| self.get_node(child_id).is_none())
+                .collect();
+
+            if let Some(node) = self.get_node(&id) {
+                let mut child_ids = node.child_ids_vec();
+                for child_id in new_child_ids {
+                    if !child_ids.contains(&child_id) {
+                        child_ids.push(child_id);
+                    }
+                }
+                let parent_id = node.parent_id();
+                let mut merged_node = resolve(node, &other_node);
+                merged_node.set_child_ids(child_ids);
+                if let Some(parent_id) = parent_id {
+                    merged_node.set_parent_id(parent_id);
+                } else {
+                    merged_node.remove_parent_id();
+                }
+                if let Some(node) = self.get_mut_node(&id) {
+                    *node = merged_node;
+                }
+            } else {
+                let mut node = other_node;
+                node.set_child_ids(new_child_ids);
+                if let Some(parent_node) = node.parent_id().and_then(|id| self.get_mut_node(&id)) {
+                    if !parent_node.child_ids_vec().contains(&id) {
+                        parent_node.add_child_id(id.clone());
+                    }
+                }
+                self.child_nodes.insert(id, node);
+            }
+        }
+        Ok(())
+    }
+
+    // Insert all the nodes of `sub_tree` under `parent_id`, without any check
+    pub(crate) fn attach_sub_tree(&mut self, parent_id: &I, mut sub_tree: RootedTree<I, N>) {
+        if let Some(mut root_node) = sub_tree.root_node.take() {
+            root_node.set_parent_id(parent_id.clone());
+            if let Some(parent_node) = self.get_mut_node(parent_id) {
+                parent_node.add_child_id(root_node.id());
+            }
+            self.child_nodes.insert(root_node.id(), root_node);
+        }
+        self.child_nodes.extend(sub_tree.child_nodes.drain());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::*;
+
+    #[test]
+    fn move_subtree_to() {
+        let mut tree_1 = RootedTree::new();
+        tree_1.add_node(None, DataNode::new(1)).unwrap();
+        tree_1.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree_1.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        let mut tree_2 = RootedTree::new();
+        tree_2.add_node(None, DataNode::new(10)).unwrap();
+        tree_2.add_node(Some(10), DataNode::new(11)).unwrap();
+
+        tree_1.move_subtree_to(&2, &mut tree_2, &11).unwrap();
+
+        assert_eq!(tree_1.len(), 1);
+        assert_eq!(
+            tree_1.get_node(&1).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
+
+        assert_eq!(tree_2.len(), 4);
+        assert_eq!(tree_2.get_node(&11).unwrap().child_ids_vec(), vec![2]);
+        assert_eq!(tree_2.get_node(&2).unwrap().parent_id(), Some(11));
+        assert_eq!(tree_2.get_node(&2).unwrap().child_ids_vec(), vec![3]);
+        assert_eq!(tree_2.get_node(&3).unwrap().parent_id(), Some(2));
+    }
+
+    #[test]
+    fn fail_to_move_subtree_to() {
+        let mut tree_1 = RootedTree::new();
+        tree_1.add_node(None, DataNode::new(1)).unwrap();
+        tree_1.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree_1.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        let mut tree_2 = RootedTree::new();
+        tree_2.add_node(None, DataNode::new(10)).unwrap();
+        tree_2.add_node(Some(10), DataNode::new(3)).unwrap();
+
+        assert!(matches!(
+            tree_1.move_subtree_to(&2, &mut tree_2, &10),
+            Err(Error::NodeAlreadyExists)
+        ));
+        assert!(matches!(
+            tree_1.move_subtree_to(&2, &mut tree_2, &11),
+            Err(Error::ParentNodeDoesNotExist)
+        ));
+        assert!(matches!(
+            tree_1.move_subtree_to(&4, &mut tree_2, &10),
+            Err(Error::NodeDoesNotExist)
+        ));
+        assert_eq!(tree_1.len(), 3);
+        assert_eq!(tree_2.len(), 2);
+    }
+
+    #[test]
+    fn move_partial_subtree_to() {
+        let mut tree_1 = RootedTree::new();
+        tree_1.add_node(None, DataNode::new(1)).unwrap();
+        let mut node = DataNode::new(2);
+        node.add_child_id(11);
+        tree_1.add_node(Some(1), node).unwrap();
+
+        let mut tree_2 = RootedTree::new();
+        tree_2.add_node(None, DataNode::new(10)).unwrap();
+        tree_2.add_node(Some(10), DataNode::new(11)).unwrap();
+
+        assert!(matches!(
+            tree_1.move_subtree_to(&2, &mut tree_2, &10),
+            Err(Error::NodeAlreadyExists)
+        ));
+        assert_eq!(tree_1.len(), 2);
+        assert_eq!(tree_2.len(), 2);
+        assert!(tree_2.validate().is_ok());
+
+        tree_1.get_mut_node(&2).unwrap().remove_child_id(&11);
+        tree_1.get_mut_node(&2).unwrap().add_child_id(12);
+        tree_1.move_subtree_to(&2, &mut tree_2, &10).unwrap();
+
+        assert_eq!(tree_1.len(), 1);
+        assert_eq!(tree_2.len(), 3);
+        assert_eq!(tree_2.get_node(&2).unwrap().child_ids_vec(), vec![12]);
+        assert!(tree_2.validate().is_ok());
+    }
+
+    #[test]
+    fn graft() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+
+        let mut sub_tree = RootedTree::new();
+        sub_tree.add_node(None, DataNode::new(10)).unwrap();
+        sub_tree.add_node(Some(10), DataNode::new(11)).unwrap();
+
+        tree.graft(&2, sub_tree).unwrap();
+
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.get_node(&2).unwrap().child_ids_vec(), vec![10]);
+        assert_eq!(tree.get_node(&10).unwrap().parent_id(), Some(2));
+        assert_eq!(tree.get_node(&11).unwrap().parent_id(), Some(10));
+    }
+
+    #[test]
+    fn graft_errors() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+
+        let mut sub_tree = RootedTree::new();
+        sub_tree.add_node(None, DataNode::new(10)).unwrap();
+        sub_tree.add_node(Some(10), DataNode::new(2)).unwrap();
+
+        assert!(matches!(
+            tree.graft(&3, RootedTree::new()),
+            Err(Error::ParentNodeDoesNotExist)
+        ));
+        assert!(matches!(
+            tree.graft(&2, sub_tree),
+            Err(Error::NodeAlreadyExists)
+        ));
+        assert_eq!(tree.len(), 2);
+        assert_eq!(
+            tree.get_node(&2).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
+    }
+
+    #[test]
+    fn merge_with() {
+        let mut tree_1 = RootedTree::new();
+        tree_1.add_node(None, DataNode::new(1)).unwrap();
+        tree_1.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree_1.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree_1.get_mut_node(&2).unwrap().value = 10;
+
+        let mut tree_2 = RootedTree::new();
+        tree_2.add_node(None, DataNode::new(1)).unwrap();
+        tree_2.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree_2.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree_2.add_node(Some(1), DataNode::new(5)).unwrap();
+        tree_2.get_mut_node(&2).unwrap().value = 5;
+        tree_2.get_mut_node(&5).unwrap().value = 7;
+
+        tree_1
+            .merge_with(tree_2, |a, b| {
+                let mut node = a.clone();
+                node.value = a.value + b.value;
+                node
+            })
+            .unwrap();
+
+        assert_eq!(tree_1.len(), 5);
+        assert_eq!(tree_1.get_node(&1).unwrap().child_ids_vec(), vec![2, 5]);
+        assert_eq!(tree_1.get_node(&2).unwrap().child_ids_vec(), vec![3, 4]);
+        assert_eq!(tree_1.get_node(&2).unwrap().value, 15);
+        assert_eq!(tree_1.get_node(&4).unwrap().parent_id(), Some(2));
+        assert_eq!(tree_1.get_node(&5).unwrap().value, 7);
+    }
+
+    #[test]
+    fn merge_with_disjoint() {
+        let mut tree_1 = RootedTree::new();
+        tree_1.add_node(None, DataNode::new(1)).unwrap();
+
+        let mut tree_2 = RootedTree::new();
+        tree_2.add_node(None, DataNode::new(10)).unwrap();
+
+        assert!(matches!(
+            tree_1.merge_with(tree_2, |a, _| a.clone()),
+            Err(Error::NodeDoesNotExist)
+        ));
+        assert_eq!(tree_1.len(), 1);
+    }
+
+    #[test]
+    fn merge_with_conflicting_parent() {
+        let mut tree_1 = RootedTree::new();
+        tree_1.add_node(None, DataNode::new(1)).unwrap();
+        tree_1.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree_1.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        let mut tree_2 = RootedTree::new();
+        tree_2.add_node(None, DataNode::new(1)).unwrap();
+        tree_2.add_node(Some(1), DataNode::new(4)).unwrap();
+        tree_2.add_node(Some(4), DataNode::new(3)).unwrap();
+
+        assert!(matches!(
+            tree_1.merge_with(tree_2, |a, _| a.clone()),
+            Err(Error::NodeAlreadyExists)
+        ));
+        assert_eq!(tree_1.len(), 3);
+        assert!(tree_1.get_node(&4).is_none());
+        assert_eq!(tree_1.get_node(&3).unwrap().parent_id(), Some(2));
+    }
+}
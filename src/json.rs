@@ -0,0 +1,131 @@
+use crate::{Node, Result, RootedTree};
+use serde::de::{DeserializeOwned, Error as _};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::hash::Hash;
+
+impl<I: Eq + PartialEq + Clone + Hash + Serialize, N: Node<I>> RootedTree<I, N> {
+    /// Nested `{ "id": ..., "children": [...] }` representation starting from the root, `null`
+    /// for an empty tree. Missing child nodes are emitted as `{ "id": ..., "missing": true }`.
+    pub fn to_json_nested(&self) -> Value {
+        if let Some(root_node) = &self.root_node {
+            self.node_to_json(root_node)
+        } else {
+            Value::Null
+        }
+    }
+
+    fn node_to_json(&self, node: &N) -> Value {
+        let children: Vec<Value> = node
+            .child_ids_vec()
+            .iter()
+            .map(|child_id| {
+                if let Some(child) = self.child_nodes.get(child_id) {
+                    self.node_to_json(child)
+                } else {
+                    json!({ "id": child_id, "missing": true })
+                }
+            })
+            .collect();
+        json!({ "id": node.id(), "children": children })
+    }
+}
+
+impl<I: Eq + PartialEq + Clone + Hash + DeserializeOwned, N: Node<I>> RootedTree<I, N> {
+    /// Rebuild a tree from `to_json_nested`, creating each node from its id with `new_node`.
+    pub fn from_json_nested<F: Fn(I) -> N>(value: &Value, new_node: F) -> Result<Self> {
+        let mut rooted_tree = RootedTree::new();
+        if !value.is_null() {
+            rooted_tree.add_json_node(None, value, &new_node)?;
+        }
+        Ok(rooted_tree)
+    }
+
+    fn add_json_node<F: Fn(I) -> N>(
+        &mut self,
+        parent_id: Option<I>,
+        value: &Value,
+        new_node: &F,
+    ) -> Result<()> {
+        let id: I = serde_json::from_value(value["id"].clone())?;
+        if value["missing"] == Value::Bool(true) {
+            if let Some(parent_node) = parent_id.and_then(|parent_id| self.get_mut_node(&parent_id))
+            {
+                parent_node.add_child_id(id);
+            }
+            return Ok(());
+        }
+
+        self.add_node(parent_id, new_node(id.clone()))?;
+        match &value["children"] {
+            Value::Null => {}
+            Value::Array(children) => {
+                for child in children {
+                    self.add_json_node(Some(id.clone()), child, new_node)?;
+                }
+            }
+            _ => return Err(serde_json::Error::custom("children must be an array").into()),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::*;
+
+    #[test]
+    fn json_nested_round_trip() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(1), DataNode::new(4)).unwrap();
+
+        let value = tree.to_json_nested();
+        assert_eq!(
+            value,
+            json!({ "id": 1, "children": [
+                { "id": 2, "children": [{ "id": 3, "children": [] }] },
+                { "id": 4, "children": [] },
+            ]})
+        );
+
+        let from_json = RootedTree::from_json_nested(&value, DataNode::new).unwrap();
+        assert!(tree == from_json);
+    }
+
+    #[test]
+    fn json_nested_partial() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        let mut node = DataNode::new(2);
+        node.add_child_id(3);
+        tree.add_node(Some(1), node).unwrap();
+
+        let value = tree.to_json_nested();
+        assert_eq!(
+            value,
+            json!({ "id": 1, "children": [
+                { "id": 2, "children": [{ "id": 3, "missing": true }] },
+            ]})
+        );
+
+        let from_json = RootedTree::from_json_nested(&value, DataNode::new).unwrap();
+        assert_eq!(from_json.len(), 2);
+        assert_eq!(from_json.get_node(&2).unwrap().child_ids_vec(), vec![3]);
+    }
+
+    #[test]
+    fn json_nested_empty() {
+        let tree = RootedTree::<i32, DataNode>::new();
+        assert_eq!(tree.to_json_nested(), Value::Null);
+        assert_eq!(
+            RootedTree::from_json_nested(&Value::Null, DataNode::new)
+                .unwrap()
+                .len(),
+            0
+        );
+    }
+}
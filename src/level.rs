@@ -0,0 +1,469 @@
+use crate::{Node, RootedTree};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+impl<I: Eq + PartialEq + Clone + Hash, N: Node<I>> RootedTree<I, N> {
+    /// Ids of all nodes exactly `lvl` levels below the root, in level-order.
+    pub fn ids_at_depth(&self, lvl: u32) -> Vec<I> {
+        let mut ids = if let Some(root_node) = &self.root_node {
+            vec![root_node.id()]
+        } else {
+            return vec![];
+        };
+
+        for _ in 0..lvl {
+            let mut next_ids = vec![];
+            for id in &ids {
+                if let Some(node) = self.get_node(id) {
+                    for child_id in node.child_ids_vec() {
+                        if self.child_nodes.contains_key(&child_id) {
+                            next_ids.push(child_id);
+                        }
+                    }
+                }
+            }
+            if next_ids.is_empty() {
+                return vec![];
+            }
+            ids = next_ids;
+        }
+        ids
+    }
+
+    /// Number of edges from the root of the tree down to `id`, the root being at depth 0.
+    ///
+    /// For a subtree, the depth is counted from its own root, not from the parent outside the
+    /// tree.
+    pub fn depth(&self, id: &I) -> Option<u32> {
+        Some(self.path_from_root(id)?.len() as u32 - 1)
+    }
+
+    /// Number of edges of the longest path from the root down to a leaf.
+    pub fn height(&self) -> u32 {
+        if let Some(root_node) = &self.root_node {
+            self.heights().get(&root_node.id()).copied().unwrap_or(0)
+        } else {
+            0
+        }
+    }
+
+    /// Ids of the descendants of `id` between `min_lvl` and `max_lvl` levels below it, both
+    /// included, in level-order.
+    pub fn descendants_between(&self, id: &I, min_lvl: u32, max_lvl: u32) -> Vec<I> {
+        let mut ids = if self.get_node(id).is_some() {
+            vec![id.clone()]
+        } else {
+            return vec![];
+        };
+
+        let mut out = vec![];
+        for lvl in 1..=max_lvl {
+            let mut next_ids = vec![];
+            for id in &ids {
+                if let Some(node) = self.get_node(id) {
+                    for child_id in node.child_ids_vec() {
+                        if self.child_nodes.contains_key(&child_id) {
+                            next_ids.push(child_id);
+                        }
+                    }
+                }
+            }
+            if next_ids.is_empty() {
+                break;
+            }
+            if lvl >= min_lvl {
+                out.extend(next_ids.iter().cloned());
+            }
+            ids = next_ids;
+        }
+        out
+    }
+
+    /// Lowest common ancestor of `a` and `b`, which is `a` itself when it is an ancestor of `b`.
+    pub fn lca(&self, a: &I, b: &I) -> Option<I> {
+        let path_a = self.path_from_root(a)?;
+        let path_b = self.path_from_root(b)?;
+        path_a
+            .into_iter()
+            .zip(path_b)
+            .take_while(|(a, b)| a == b)
+            .last()
+            .map(|(id, _)| id)
+    }
+
+    /// Ids from `a` up to the lowest common ancestor and back down to `b`, both included.
+    pub fn path_between(&self, a: &I, b: &I) -> Option<Vec<I>> {
+        let path_a = self.path_from_root(a)?;
+        let path_b = self.path_from_root(b)?;
+        let common_len = path_a
+            .iter()
+            .zip(path_b.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let mut path: Vec<I> = path_a[common_len.checked_sub(1)?..]
+            .iter()
+            .rev()
+            .cloned()
+            .collect();
+        path.extend(path_b[common_len..].iter().cloned());
+        Some(path)
+    }
+
+    /// Number of levels, starting at the root, holding the same ids under the same parents in the
+    /// same order in both trees.
+    pub fn shared_prefix_height(&self, other: &RootedTree<I, N>) -> u32 {
+        let root_level = |tree: &RootedTree<I, N>| -> Vec<(Option<I>, I)> {
+            tree.root_node
+                .iter()
+                .map(|root_node| (None, root_node.id()))
+                .collect()
+        };
+        let mut level = root_level(self);
+        let mut other_level = root_level(other);
+        let mut lvl = 0;
+        while !level.is_empty() && level == other_level {
+            lvl += 1;
+            level = self.next_level(&level);
+            other_level = other.next_level(&other_level);
+        }
+        lvl
+    }
+
+    // (parent id, id) of the nodes one level below `level`, in level-order
+    fn next_level(&self, level: &[(Option<I>, I)]) -> Vec<(Option<I>, I)> {
+        let mut next_level = vec![];
+        for (_, id) in level {
+            if let Some(node) = self.get_node(id) {
+                for child_id in node.child_ids_vec() {
+                    if self.child_nodes.contains_key(&child_id) {
+                        next_level.push((Some(id.clone()), child_id));
+                    }
+                }
+            }
+        }
+        next_level
+    }
+
+    /// Depth of the deepest node shared by the root paths of all `ids`.
+    pub fn common_prefix_depth(&self, ids: &[I]) -> Option<u32> {
+        let mut paths = ids.iter().map(|id| self.path_from_root(id));
+        let mut common_path = paths.next()??;
+        for path in paths {
+            let path = path?;
+            let common_len = common_path
+                .iter()
+                .zip(path.iter())
+                .take_while(|(a, b)| a == b)
+                .count();
+            common_path.truncate(common_len);
+        }
+        (common_path.len() as u32).checked_sub(1)
+    }
+
+    /// Nodes whose children subtrees heights differ by more than one, with the difference,
+    /// in level-order.
+    pub fn imbalances(&self) -> Vec<(I, u32)> {
+        let heights = self.heights();
+        let mut out = vec![];
+        if let Some(root_node) = &self.root_node {
+            for node in self.bfs_from(&root_node.id()) {
+                let child_heights: Vec<u32> = node
+                    .child_ids_vec()
+                    .iter()
+                    .filter_map(|child_id| heights.get(child_id).copied())
+                    .collect();
+                if let (Some(max), Some(min)) =
+                    (child_heights.iter().max(), child_heights.iter().min())
+                {
+                    if max - min > 1 {
+                        out.push((node.id(), max - min));
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// `subtree_size` of every node, computed in a single bottom-up pass.
+    pub fn subtree_size_map(&self) -> HashMap<I, usize> {
+        let mut sizes = HashMap::new();
+        let nodes: Vec<&N> = if let Some(root_node) = &self.root_node {
+            self.bfs_from(&root_node.id()).collect()
+        } else {
+            vec![]
+        };
+        for node in nodes.into_iter().rev() {
+            let size = node
+                .child_ids_vec()
+                .iter()
+                .filter_map(|child_id| sizes.get(child_id))
+                .sum::<usize>()
+                + 1;
+            sizes.insert(node.id(), size);
+        }
+        sizes
+    }
+
+    // Height of the subtree of every node, leaves have a height of 0
+    pub(crate) fn heights(&self) -> HashMap<I, u32> {
+        let mut heights = HashMap::new();
+        let nodes: Vec<&N> = if let Some(root_node) = &self.root_node {
+            self.bfs_from(&root_node.id()).collect()
+        } else {
+            vec![]
+        };
+        for node in nodes.into_iter().rev() {
+            let height = node
+                .child_ids_vec()
+                .iter()
+                .filter_map(|child_id| heights.get(child_id))
+                .max()
+                .map_or(0, |height| height + 1);
+            heights.insert(node.id(), height);
+        }
+        heights
+    }
+
+    // Ids from the root of the tree down to `id` included
+    pub(crate) fn path_from_root(&self, id: &I) -> Option<Vec<I>> {
+        let mut node = self.get_node(id)?;
+        let mut path = vec![node.id()];
+        while let Some(parent_node) = node.parent_id().and_then(|id| self.get_node(&id)) {
+            path.push(parent_node.id());
+            node = parent_node;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::*;
+
+    fn tree() -> RootedTree<i32, DataNode> {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(3), DataNode::new(5)).unwrap();
+        tree
+    }
+
+    #[test]
+    fn ids_at_depth_root() {
+        let tree = tree();
+        assert_eq!(tree.ids_at_depth(0), vec![1]);
+    }
+
+    #[test]
+    fn ids_at_depth_children() {
+        let tree = tree();
+        assert_eq!(tree.ids_at_depth(1), vec![2, 3]);
+        assert_eq!(tree.ids_at_depth(2), vec![4, 5]);
+    }
+
+    #[test]
+    fn ids_at_depth_out_of_range() {
+        let tree = tree();
+        assert_eq!(tree.ids_at_depth(3), Vec::<i32>::new());
+
+        let tree = RootedTree::<i32, DataNode>::new();
+        assert_eq!(tree.ids_at_depth(0), Vec::<i32>::new());
+    }
+
+    fn linear_tree() -> RootedTree<i32, DataNode> {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        for id in 2..=5 {
+            tree.add_node(Some(id - 1), DataNode::new(id)).unwrap();
+        }
+        tree
+    }
+
+    #[test]
+    fn depth_and_height_linear() {
+        let tree = linear_tree();
+        assert_eq!(tree.depth(&1), Some(0));
+        assert_eq!(tree.depth(&3), Some(2));
+        assert_eq!(tree.depth(&5), Some(4));
+        assert_eq!(tree.depth(&6), None);
+        assert_eq!(tree.height(), 4);
+
+        assert_eq!(RootedTree::<i32, DataNode>::new().height(), 0);
+    }
+
+    #[test]
+    fn depth_and_height_nested() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        for (parent_id, ids) in [
+            (1, [2, 3, 4]),
+            (2, [5, 6, 7]),
+            (6, [8, 9, 10]),
+            (4, [11, 12, 13]),
+            (10, [14, 15, 16]),
+        ] {
+            for id in ids {
+                tree.add_node(Some(parent_id), DataNode::new(id)).unwrap();
+            }
+        }
+
+        assert_eq!(tree.depth(&4), Some(1));
+        assert_eq!(tree.depth(&13), Some(2));
+        assert_eq!(tree.depth(&16), Some(4));
+        assert_eq!(tree.height(), 4);
+    }
+
+    #[test]
+    fn depth_subtree() {
+        let mut tree = RootedTree::new();
+        let mut node = DataNode::new(1);
+        node.set_parent_id(0);
+        tree.set_root_node(node);
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+
+        assert_eq!(tree.depth(&1), Some(0));
+        assert_eq!(tree.depth(&2), Some(1));
+        assert_eq!(tree.height(), 1);
+    }
+
+    #[test]
+    fn subtree_size_map() {
+        let mut tree = tree();
+        tree.add_node(Some(4), DataNode::new(6)).unwrap();
+        tree.add_node(Some(4), DataNode::new(7)).unwrap();
+        tree.get_mut_node(&5).unwrap().child_ids.push(8);
+
+        let sizes = tree.subtree_size_map();
+        assert_eq!(sizes.len(), tree.len());
+        for (id, size) in &sizes {
+            assert_eq!(tree.subtree_size(id), Some(*size));
+        }
+        assert_eq!(sizes[&1], 7);
+        assert_eq!(sizes[&2], 4);
+        assert_eq!(sizes[&5], 1);
+
+        assert!(RootedTree::<i32, DataNode>::new()
+            .subtree_size_map()
+            .is_empty());
+    }
+
+    #[test]
+    fn descendants_between() {
+        let mut tree = tree();
+        tree.add_node(Some(4), DataNode::new(6)).unwrap();
+
+        assert_eq!(tree.descendants_between(&1, 2, 2), vec![4, 5]);
+        assert_eq!(tree.descendants_between(&1, 2, 5), vec![4, 5, 6]);
+        assert_eq!(tree.descendants_between(&1, 0, 1), vec![2, 3]);
+        assert_eq!(tree.descendants_between(&2, 1, 2), vec![4, 6]);
+        assert_eq!(tree.descendants_between(&1, 3, 2), Vec::<i32>::new());
+        assert_eq!(tree.descendants_between(&10, 1, 2), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn lca() {
+        let mut tree = tree();
+        tree.add_node(Some(4), DataNode::new(6)).unwrap();
+        tree.add_node(Some(4), DataNode::new(7)).unwrap();
+
+        assert_eq!(tree.lca(&2, &3), Some(1));
+        assert_eq!(tree.lca(&6, &7), Some(4));
+        assert_eq!(tree.lca(&6, &5), Some(1));
+        assert_eq!(tree.lca(&2, &6), Some(2));
+        assert_eq!(tree.lca(&7, &1), Some(1));
+        assert_eq!(tree.lca(&4, &4), Some(4));
+        assert_eq!(tree.lca(&4, &10), None);
+        assert_eq!(tree.lca(&10, &4), None);
+    }
+
+    #[test]
+    fn path_between() {
+        let mut tree = tree();
+        tree.add_node(Some(4), DataNode::new(6)).unwrap();
+
+        assert_eq!(tree.path_between(&6, &5), Some(vec![6, 4, 2, 1, 3, 5]));
+        assert_eq!(tree.path_between(&5, &3), Some(vec![5, 3]));
+        assert_eq!(tree.path_between(&1, &6), Some(vec![1, 2, 4, 6]));
+        assert_eq!(tree.path_between(&4, &4), Some(vec![4]));
+        assert_eq!(tree.path_between(&4, &10), None);
+    }
+
+    #[test]
+    fn shared_prefix_height() {
+        assert_eq!(tree().shared_prefix_height(&tree()), 3);
+
+        let mut other = tree();
+        other.add_node(Some(5), DataNode::new(6)).unwrap();
+        assert_eq!(tree().shared_prefix_height(&other), 3);
+
+        let mut other = tree();
+        other.add_node(Some(2), DataNode::new(6)).unwrap();
+        assert_eq!(tree().shared_prefix_height(&other), 2);
+
+        let mut other = RootedTree::new();
+        other.add_node(None, DataNode::new(10)).unwrap();
+        assert_eq!(tree().shared_prefix_height(&other), 0);
+        assert_eq!(tree().shared_prefix_height(&RootedTree::new()), 0);
+    }
+
+    #[test]
+    fn shared_prefix_height_different_parents() {
+        let mut tree_1 = RootedTree::new();
+        tree_1.add_node(None, DataNode::new(1)).unwrap();
+        tree_1.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree_1.add_node(Some(1), DataNode::new(3)).unwrap();
+        let mut tree_2 = tree_1.clone();
+        tree_1.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree_2.add_node(Some(3), DataNode::new(4)).unwrap();
+
+        assert_eq!(tree_1.shared_prefix_height(&tree_2), 2);
+    }
+
+    #[test]
+    fn common_prefix_depth_root() {
+        let tree = tree();
+        assert_eq!(tree.common_prefix_depth(&[4, 5]), Some(0));
+        assert_eq!(tree.common_prefix_depth(&[1, 5]), Some(0));
+    }
+
+    #[test]
+    fn common_prefix_depth_deep() {
+        let mut tree = tree();
+        tree.add_node(Some(4), DataNode::new(6)).unwrap();
+        tree.add_node(Some(4), DataNode::new(7)).unwrap();
+        tree.add_node(Some(7), DataNode::new(8)).unwrap();
+
+        assert_eq!(tree.common_prefix_depth(&[6, 8]), Some(2));
+        assert_eq!(tree.common_prefix_depth(&[6, 7, 8]), Some(2));
+        assert_eq!(tree.common_prefix_depth(&[8]), Some(4));
+        assert_eq!(tree.common_prefix_depth(&[4, 8]), Some(2));
+    }
+
+    #[test]
+    fn common_prefix_depth_missing() {
+        let tree = tree();
+        assert_eq!(tree.common_prefix_depth(&[]), None);
+        assert_eq!(tree.common_prefix_depth(&[4, 10]), None);
+    }
+
+    #[test]
+    fn imbalances() {
+        assert_eq!(tree().imbalances(), vec![]);
+
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(4), DataNode::new(5)).unwrap();
+        tree.add_node(Some(5), DataNode::new(6)).unwrap();
+        tree.add_node(Some(2), DataNode::new(7)).unwrap();
+        tree.add_node(Some(4), DataNode::new(8)).unwrap();
+
+        assert_eq!(tree.imbalances(), vec![(1, 3), (2, 2)]);
+    }
+}
@@ -1,7 +1,16 @@
 mod diff;
+mod dot;
+mod filter;
+mod graft;
+#[cfg(feature = "json")]
+mod json;
+mod level;
+mod map;
 mod node;
+mod paths;
 mod report;
 mod rooted_tree;
+mod traversal;
 mod try_from;
 
 mod clone;
@@ -28,12 +37,23 @@ pub enum Error {
     ParentNodeDoesNotExist,
     #[error("Node does not exist")]
     NodeDoesNotExist,
+    #[error("Node already exists")]
+    NodeAlreadyExists,
     #[error("Parent node does not contain child")]
     ParentNodeDoesNotContainChild,
     #[error("Child node has no parent")]
     ChildNodeHasNoParent,
     #[error("Root node has parent")]
     RootNodeHasParent,
+    #[error("Cycle detected")]
+    CycleDetected,
+    #[error("Node {0} is not reachable from the root")]
+    UnreachableNode(String),
+    #[error("Node {0} does not point back to the parent listing it")]
+    InconsistentParentLink(String),
+    #[cfg(feature = "json")]
+    #[error("Json error")]
+    JsonError(#[from] serde_json::Error),
     #[error("Report error")]
     ReportError(#[from] report::Error),
 }
@@ -0,0 +1,147 @@
+use crate::{Node, RootedTree};
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+impl<I: Eq + PartialEq + Clone + Hash, N: Node<I>> RootedTree<I, N> {
+    /// Transform every node with `transform`, dropping the nodes failing `keep` with their
+    /// subtrees. Returns `None` if the root is dropped.
+    pub fn map_filter<M, F, P>(&self, transform: F, keep: P) -> Option<RootedTree<I, M>>
+    where
+        M: Node<I>,
+        F: Fn(&N) -> M,
+        P: Fn(&N) -> bool,
+    {
+        let root_node = self.root_node.as_ref().filter(|node| keep(node))?;
+        let mut rooted_tree = RootedTree::new();
+
+        let mut queue = VecDeque::from([root_node]);
+        while let Some(node) = queue.pop_front() {
+            let mut new_node = transform(node);
+            for child_id in node.child_ids_vec() {
+                if let Some(child) = self.child_nodes.get(&child_id) {
+                    if keep(child) {
+                        queue.push_back(child);
+                    } else {
+                        new_node.remove_child_id(&child_id);
+                    }
+                }
+            }
+            if rooted_tree.root_node.is_none() {
+                rooted_tree.root_node = Some(new_node);
+            } else {
+                rooted_tree.child_nodes.insert(new_node.id(), new_node);
+            }
+        }
+
+        Some(rooted_tree)
+    }
+
+    /// Copy of the tree with every id mapped by `f`, nodes built with `new_node(node, new_id)`.
+    ///
+    /// Parent ids, including the parent of a subtree root, and child ids, including missing
+    /// ones, are mapped as well. `f` is expected to be injective.
+    pub fn map_ids<J, M, F, G>(&self, f: F, new_node: G) -> RootedTree<J, M>
+    where
+        J: Eq + PartialEq + Clone + Hash,
+        M: Node<J>,
+        F: Fn(&I) -> J,
+        G: Fn(&N, J) -> M,
+    {
+        let map_node = |node: &N| {
+            let mut mapped_node = new_node(node, f(&node.id()));
+            match node.parent_id() {
+                Some(parent_id) => mapped_node.set_parent_id(f(&parent_id)),
+                None => mapped_node.remove_parent_id(),
+            }
+            mapped_node.set_child_ids(node.child_ids_vec().iter().map(&f).collect());
+            mapped_node
+        };
+
+        let mut rooted_tree = RootedTree::new();
+        rooted_tree.root_node = self.root_node.as_ref().map(map_node);
+        for node in self.child_nodes.values() {
+            let mapped_node = map_node(node);
+            rooted_tree
+                .child_nodes
+                .insert(mapped_node.id(), mapped_node);
+        }
+        rooted_tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::*;
+
+    #[test]
+    fn map_filter() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(3), DataNode::new(6)).unwrap();
+        tree.add_node(Some(4), DataNode::new(8)).unwrap();
+
+        let mapped_tree = tree
+            .map_filter(
+                |node| {
+                    let mut node = node.clone();
+                    node.value = node.id * 10;
+                    node
+                },
+                |node| node.id % 2 == 0,
+            )
+            .unwrap();
+
+        assert_eq!(mapped_tree.len(), 3);
+        assert_eq!(mapped_tree.get_node(&2).unwrap().child_ids_vec(), vec![4]);
+        assert_eq!(mapped_tree.get_node(&4).unwrap().child_ids_vec(), vec![8]);
+        assert_eq!(mapped_tree.get_node(&8).unwrap().value, 80);
+        assert!(mapped_tree.get_node(&3).is_none());
+        assert!(mapped_tree.get_node(&6).is_none());
+    }
+
+    #[test]
+    fn map_ids() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        let mut node = DataNode::new(4);
+        node.add_child_id(5);
+        tree.add_node(Some(2), node).unwrap();
+
+        let n = |id: &str| id.to_string();
+        let mapped_tree = tree.map_ids(|id| format!("n{}", id), |_, id| PathNode::new(&id));
+
+        assert_eq!(mapped_tree.len(), tree.len());
+        assert_eq!(
+            mapped_tree.get_node(&n("n1")).unwrap().child_ids_vec(),
+            vec!["n2", "n3"]
+        );
+        assert_eq!(mapped_tree.parent_id(&n("n4")), Some(n("n2")));
+        assert_eq!(
+            mapped_tree.get_node(&n("n4")).unwrap().child_ids_vec(),
+            vec!["n5"]
+        );
+        assert_eq!(
+            mapped_tree.path_from_root(&n("n4")),
+            Some(vec![n("n1"), n("n2"), n("n4")])
+        );
+
+        // The parent of a subtree root is outside of the tree
+        let sub_tree = tree.clone_from(2).unwrap();
+        let mapped_sub_tree = sub_tree.map_ids(|id| format!("n{}", id), |_, id| PathNode::new(&id));
+        assert_eq!(mapped_sub_tree.parent_id(&n("n2")), Some(n("n1")));
+        assert!(mapped_sub_tree.is_subtree());
+    }
+
+    #[test]
+    fn map_filter_drop_root() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+
+        assert!(tree.map_filter(|node| node.clone(), |_| false).is_none());
+    }
+}
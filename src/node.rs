@@ -3,6 +3,22 @@ pub trait Node<I> {
     fn parent_id(&self) -> Option<I>;
     fn child_ids_vec(&self) -> Vec<I>;
     fn set_parent_id(&mut self, parent: I);
+    /// Clear the parent id, `parent_id` must return `None` afterwards.
+    fn remove_parent_id(&mut self);
     fn add_child_id(&mut self, child_id: I);
     fn remove_child_id(&mut self, child_id: &I);
+
+    /// Label rendered on the branch leading to this node in reports.
+    fn edge_label(&self) -> Option<String> {
+        None
+    }
+
+    fn set_child_ids(&mut self, child_ids: Vec<I>) {
+        for child_id in self.child_ids_vec() {
+            self.remove_child_id(&child_id);
+        }
+        for child_id in child_ids {
+            self.add_child_id(child_id);
+        }
+    }
 }
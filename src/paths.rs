@@ -0,0 +1,149 @@
+use crate::{Node, Result, RootedTree};
+
+impl<N: Node<String>> RootedTree<String, N> {
+    /// Build the tree of the absolute `paths` rooted at `"/"`, with a node per path prefix.
+    ///
+    /// Nodes are created with `new_node(path)`, children in the order the paths come.
+    pub fn from_paths<F: Fn(&str) -> N>(paths: &[&str], new_node: F) -> Result<Self> {
+        let mut rooted_tree = RootedTree::new();
+        rooted_tree.add_node(None, new_node("/"))?;
+        for path in paths {
+            let prefixes = path.split('/').filter(|segment| !segment.is_empty()).scan(
+                String::new(),
+                |prefix, segment| {
+                    prefix.push('/');
+                    prefix.push_str(segment);
+                    Some(prefix.clone())
+                },
+            );
+            rooted_tree.add_prefixes("/", prefixes, &new_node)?;
+        }
+        Ok(rooted_tree)
+    }
+
+    /// Build the tree of the `entries` split on `sep`, e.g. dotted keys, with a node per prefix
+    /// keyed by the full prefix, under a root keyed by the empty prefix `""`.
+    ///
+    /// Nodes are created with `new_node(prefix)`, children in the order the entries come.
+    pub fn from_delimited<F: Fn(&str) -> N>(
+        entries: &[&str],
+        sep: char,
+        new_node: F,
+    ) -> Result<Self> {
+        let mut rooted_tree = RootedTree::new();
+        rooted_tree.add_node(None, new_node(""))?;
+        for entry in entries {
+            let prefixes = entry.split(sep).filter(|segment| !segment.is_empty()).scan(
+                String::new(),
+                |prefix, segment| {
+                    if !prefix.is_empty() {
+                        prefix.push(sep);
+                    }
+                    prefix.push_str(segment);
+                    Some(prefix.clone())
+                },
+            );
+            rooted_tree.add_prefixes("", prefixes, &new_node)?;
+        }
+        Ok(rooted_tree)
+    }
+
+    // Add the missing nodes of the chain of `prefixes` below `root_id`
+    fn add_prefixes<F: Fn(&str) -> N>(
+        &mut self,
+        root_id: &str,
+        prefixes: impl Iterator<Item = String>,
+        new_node: &F,
+    ) -> Result<()> {
+        let mut parent_id = root_id.to_string();
+        for id in prefixes {
+            if self.get_node(&id).is_none() {
+                self.add_node(Some(parent_id), new_node(&id))?;
+            }
+            parent_id = id;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_data::*;
+    use crate::*;
+
+    #[test]
+    fn from_paths() {
+        let tree = RootedTree::from_paths(
+            &[
+                "/home/alice/Documents",
+                "/home/bob/Documents",
+                "/etc/hosts",
+                "/home/",
+            ],
+            PathNode::new,
+        )
+        .unwrap();
+
+        assert!(tree == path_tree());
+        assert_eq!(
+            tree.get_node(&"/home".to_string()).unwrap().child_ids_vec(),
+            vec!["/home/alice", "/home/bob"]
+        );
+        assert_eq!(
+            tree.parent_id(&"/home/bob/Documents".to_string()),
+            Some("/home/bob".to_string())
+        );
+    }
+
+    #[test]
+    fn from_delimited() {
+        let tree = RootedTree::from_delimited(
+            &[
+                "server.http.port",
+                "server.http.host",
+                "log.level",
+                "server.name",
+            ],
+            '.',
+            PathNode::new,
+        )
+        .unwrap();
+
+        assert_eq!(tree.len(), 8);
+        assert_eq!(
+            tree.get_node(&"".to_string()).unwrap().child_ids_vec(),
+            vec!["server", "log"]
+        );
+        assert_eq!(
+            tree.get_node(&"server".to_string())
+                .unwrap()
+                .child_ids_vec(),
+            vec!["server.http", "server.name"]
+        );
+        assert_eq!(
+            tree.parent_id(&"server.http.port".to_string()),
+            Some("server.http".to_string())
+        );
+    }
+
+    #[test]
+    fn from_delimited_duplicate_prefixes() {
+        let tree =
+            RootedTree::from_delimited(&["a/b/c", "a/b", "a//b/c/", "a/b/c"], '/', PathNode::new)
+                .unwrap();
+
+        assert_eq!(tree.len(), 4);
+        assert_eq!(
+            tree.get_node(&"a/b".to_string()).unwrap().child_ids_vec(),
+            vec!["a/b/c"]
+        );
+        assert_eq!(tree.path_from_root(&"a/b/c".to_string()).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn from_paths_empty() {
+        let tree = RootedTree::from_paths(&[], PathNode::new).unwrap();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.get_node(&"/".to_string()).unwrap().parent_id(), None);
+    }
+}
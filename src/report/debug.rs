@@ -2,7 +2,7 @@ use crate::{Node, RootedTree};
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
 
-impl<I: Eq + PartialEq + Clone + Hash + Ord + Display, N: Node<I> + Clone> Debug
+impl<I: Eq + PartialEq + Clone + Hash + Ord + Display, N: Node<I> + Clone + 'static> Debug
     for RootedTree<I, N>
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -2,7 +2,7 @@ use crate::{Config, Node, RootedTree};
 use std::fmt::Display;
 use std::hash::Hash;
 
-impl<I: Eq + PartialEq + Clone + Hash + Ord + Display, N: Node<I> + Clone> Display
+impl<I: Eq + PartialEq + Clone + Hash + Ord + Display, N: Node<I> + Clone + 'static> Display
     for RootedTree<I, N>
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -1,14 +1,74 @@
 use std::fmt::Display;
+use unicode_width::UnicodeWidthStr;
+
+/// Glyphs used to draw the branches of a report, each one expected to be one column wide.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GlyphSet {
+    pub vertical: String,
+    pub heavy_vertical: String,
+    pub tee: String,
+    pub elbow: String,
+    pub top_elbow: String,
+    pub down_tee: String,
+    pub dash: String,
+    pub dashed_vertical: String,
+    pub dashed_dash: String,
+    pub space: String,
+    pub vertical_ellipsis: String,
+}
+
+impl GlyphSet {
+    pub fn unicode() -> Self {
+        Self {
+            vertical: "│".to_string(),
+            heavy_vertical: "┃".to_string(),
+            tee: "├".to_string(),
+            elbow: "└".to_string(),
+            top_elbow: "┌".to_string(),
+            down_tee: "┬".to_string(),
+            dash: "─".to_string(),
+            dashed_vertical: "╎".to_string(),
+            dashed_dash: "╌".to_string(),
+            space: " ".to_string(),
+            vertical_ellipsis: "⋮".to_string(),
+        }
+    }
+
+    pub fn ascii() -> Self {
+        Self {
+            vertical: "|".to_string(),
+            heavy_vertical: "#".to_string(),
+            tee: "+".to_string(),
+            elbow: "`".to_string(),
+            top_elbow: ",".to_string(),
+            down_tee: "+".to_string(),
+            dash: "-".to_string(),
+            dashed_vertical: ":".to_string(),
+            dashed_dash: ".".to_string(),
+            space: " ".to_string(),
+            vertical_ellipsis: ":".to_string(),
+        }
+    }
+}
+
+impl Default for GlyphSet {
+    fn default() -> Self {
+        Self::unicode()
+    }
+}
 
 #[derive(Clone)]
 pub(crate) enum LvlChar {
     Space(u32),
     SolidBar(u32),
+    HeavyBar(u32),
     SolidAngle(u32),
     SolidDashAngle(u32),
     SolidCross(u32),
     SolidDashCross(u32),
     DashBar(u32),
+    // Followed by extra spaces
+    Padded(Box<LvlChar>, usize),
     Empty,
 }
 
@@ -19,60 +79,137 @@ impl LvlChar {
         }
         (len as i32 + delta) as usize
     }
+
+    pub(crate) fn display<'a>(&'a self, glyph_set: &'a GlyphSet) -> LvlCharDisplay<'a> {
+        LvlCharDisplay {
+            lvl_char: self,
+            glyph_set,
+            label: None,
+        }
+    }
+
+    // Display with a label on the branch, only rendered by angles and crosses
+    pub(crate) fn display_labeled<'a>(
+        &'a self,
+        glyph_set: &'a GlyphSet,
+        label: &'a str,
+    ) -> LvlCharDisplay<'a> {
+        LvlCharDisplay {
+            lvl_char: self,
+            glyph_set,
+            label: Some(label),
+        }
+    }
+
+    // Width added to a branch by `display_labeled`
+    pub(crate) fn label_width(label: &str) -> usize {
+        UnicodeWidthStr::width(label) + 4
+    }
 }
 
-impl Display for LvlChar {
+pub(crate) struct LvlCharDisplay<'a> {
+    lvl_char: &'a LvlChar,
+    glyph_set: &'a GlyphSet,
+    label: Option<&'a str>,
+}
+
+impl LvlCharDisplay<'_> {
+    fn label(&self) -> String {
+        if let Some(label) = self.label {
+            format!("({}){}", label, self.glyph_set.dash.repeat(2))
+        } else {
+            "".to_string()
+        }
+    }
+}
+
+impl Display for LvlCharDisplay<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
+        let g = self.glyph_set;
+        match self.lvl_char {
             LvlChar::Space(parent_len) => {
                 write!(
                     f,
-                    "{}",
-                    format!("    {}", " ".repeat(LvlChar::real_len(-1, *parent_len)))
+                    "{}{}",
+                    g.space.repeat(4),
+                    g.space.repeat(LvlChar::real_len(-1, *parent_len))
                 )
             }
             LvlChar::SolidBar(parent_len) => {
                 write!(
                     f,
-                    "{}",
-                    format!(" │  {}", " ".repeat(LvlChar::real_len(-1, *parent_len)))
+                    "{}{}{}{}",
+                    g.space,
+                    g.vertical,
+                    g.space.repeat(2),
+                    g.space.repeat(LvlChar::real_len(-1, *parent_len))
+                )
+            }
+            LvlChar::HeavyBar(parent_len) => {
+                write!(
+                    f,
+                    "{}{}{}{}",
+                    g.space,
+                    g.heavy_vertical,
+                    g.space.repeat(2),
+                    g.space.repeat(LvlChar::real_len(-1, *parent_len))
                 )
             }
             LvlChar::SolidAngle(parent_len) => {
                 write!(
                     f,
-                    "{}",
-                    format!(" └──{}", "─".repeat(LvlChar::real_len(-1, *parent_len)))
+                    "{}{}{}{}{}",
+                    g.space,
+                    g.elbow,
+                    g.dash.repeat(2),
+                    self.label(),
+                    g.dash.repeat(LvlChar::real_len(-1, *parent_len))
                 )
             }
             LvlChar::SolidDashAngle(parent_len) => {
                 write!(
                     f,
-                    "{}",
-                    format!(" └╌╌╌╌╌╌{}", "╌".repeat(LvlChar::real_len(3, *parent_len)))
+                    "{}{}{}{}",
+                    g.space,
+                    g.elbow,
+                    g.dashed_dash.repeat(6),
+                    g.dashed_dash.repeat(LvlChar::real_len(3, *parent_len))
                 )
             }
             LvlChar::SolidCross(parent_len) => {
                 write!(
                     f,
-                    "{}",
-                    format!(" ├──{}", "─".repeat(LvlChar::real_len(-1, *parent_len)))
+                    "{}{}{}{}{}",
+                    g.space,
+                    g.tee,
+                    g.dash.repeat(2),
+                    self.label(),
+                    g.dash.repeat(LvlChar::real_len(-1, *parent_len))
                 )
             }
             LvlChar::SolidDashCross(parent_len) => {
                 write!(
                     f,
-                    "{}",
-                    format!(" ├╌╌╌╌╌╌{}", "╌".repeat(LvlChar::real_len(3, *parent_len)))
+                    "{}{}{}{}",
+                    g.space,
+                    g.tee,
+                    g.dashed_dash.repeat(6),
+                    g.dashed_dash.repeat(LvlChar::real_len(3, *parent_len))
                 )
             }
             LvlChar::DashBar(parent_len) => {
                 write!(
                     f,
-                    "{}",
-                    format!(" ╎  {}", " ".repeat(LvlChar::real_len(-1, *parent_len)))
+                    "{}{}{}{}",
+                    g.space,
+                    g.dashed_vertical,
+                    g.space.repeat(2),
+                    g.space.repeat(LvlChar::real_len(-1, *parent_len))
                 )
             }
+            LvlChar::Padded(lvl_char, len) => {
+                write!(f, "{}{}", lvl_char.display(g), g.space.repeat(*len))
+            }
             LvlChar::Empty => {
                 write!(f, "")
             }
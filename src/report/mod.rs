@@ -4,17 +4,26 @@ mod lvl_string;
 
 pub use debug::*;
 pub use display::*;
+pub use lvl_string::GlyphSet;
 
 use crate::{Node, RootedTree};
 use lvl_string::*;
+use std::any::Any;
+use std::cell::Cell;
 use std::fmt::Display;
 use std::fmt::Write;
 use std::hash::Hash;
+use std::sync::Arc;
 use thiserror::Error;
 use unicode_width::UnicodeWidthStr;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+// ANSI escape codes around the highlighted ids
+const HIGHLIGHT_START: &str = "\x1b[1;33m";
+const HIGHLIGHT_END: &str = COLOR_RESET;
+const COLOR_RESET: &str = "\x1b[0m";
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Formatting error")]
@@ -33,12 +42,115 @@ impl Default for ChildWrap {
     }
 }
 
+/// Terminal color of the branch glyphs of a level, see `Config::with_depth_gradient`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    fn ansi_start(&self) -> &'static str {
+        match self {
+            Color::Red => "\x1b[31m",
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Blue => "\x1b[34m",
+            Color::Magenta => "\x1b[35m",
+            Color::Cyan => "\x1b[36m",
+        }
+    }
+}
+
+/// Direction in which the levels of a report are laid out.
+#[derive(Clone, PartialEq, Eq, Default)]
+pub enum Layout {
+    // Root at the top, children indented below their parent
+    #[default]
+    Vertical,
+    // Root in the leftmost column, one column per level
+    Horizontal,
+}
+
+/// Function of a node, see `Config::with_right_column` and `Config::with_decorate`.
+///
+/// The node is passed as `Any` so that `Config` does not depend on the node type, the function
+/// returns `None` for nodes of another type.
+pub type NodeFn<T> = Arc<dyn Fn(&dyn Any) -> Option<T> + Send + Sync>;
+
+/// Options of `RootedTree::report`, built from `Config::default()` with the `with_*` methods.
+///
+/// ```
+/// use rooted_tree::{ChildWrap, Config};
+///
+/// let config = Config::<i32>::default()
+///     .with_max_children(2)
+///     .with_child_wrap(ChildWrap::Top)
+///     .with_select_node(3, 2);
+/// ```
 #[derive(Clone)]
 pub struct Config<I> {
     max_children: Option<u32>,
     child_wrap: ChildWrap,
     // (node_id, max_lvl_around_node)
     select_node: Option<(I, u32)>,
+    // Appended to the line of the selected node
+    select_marker: Option<String>,
+    // Rendered flush-right on each node line
+    right_column: Option<NodeFn<String>>,
+    // With `select_node`, keep only the ancestor path and collapse off-path children
+    focus_context: bool,
+    // Append the depth of each node, relative to the rendered root
+    show_depth: bool,
+    // Offset added to rendered depths, e.g. the absolute depth of a subtree root
+    absolute_depth_base: Option<u32>,
+    // Append the number of direct children as `id[n]`
+    show_child_count: bool,
+    // With `show_child_count`, also render `[0]` on leaves
+    show_zero_child_count: bool,
+    // Render an only child with a straight bar instead of the corner glyph
+    single_child_straight: bool,
+    glyph_set: GlyphSet,
+    // Draw the branches with `GlyphSet::ascii`, whatever `glyph_set` is
+    ascii: bool,
+    // Render the tree upside down, leaves at the top and the root at the bottom
+    invert_vertical: bool,
+    // Append a footer with the number of nodes hidden by `max_children`
+    show_hidden_summary: bool,
+    // Render the number of children hidden by `max_children` on the wrap line
+    show_hidden_children: bool,
+    // Separate the branches of the root with a blank line
+    spacious: bool,
+    // Number of spaces between the branch glyphs and the label of a node
+    label_gap: usize,
+    // With `select_node`, trace the path to the selected node with heavy bars
+    highlight_path: bool,
+    // With `select_node`, color the ids of the selected node and its ancestors
+    highlight: bool,
+    // (prefix, suffix) wrapped around the id of each node
+    decorate: Option<NodeFn<(String, String)>>,
+    // Render the parent of a subtree root, turn off to render it as a plain root
+    show_root_parent: bool,
+    // Stop rendering after this many nodes, filled in level-order
+    max_nodes: Option<usize>,
+    // Replace runs of single-child nodes by an ellipsis down to the end of the run
+    chain_ellipsis: bool,
+    // Start each node line with `⟦id⟧`, padded so that the tree stays aligned
+    emit_anchors: bool,
+    // Prepended to the line of the root only
+    root_prefix: Option<String>,
+    // Only the glyphs, `ascii` and `max_nodes` apply to `Layout::Horizontal`
+    layout: Layout,
+    // Colors of the branch glyphs of each level, cycling through the palette
+    depth_gradient: Option<Vec<Color>>,
+    // Expand the path from the root to each of these nodes, collapsing the other children
+    select_nodes_multi: Vec<I>,
+    // Start the output with a new line before the root line
+    leading_newline: bool,
 }
 
 impl<I> Default for Config<I> {
@@ -47,70 +159,696 @@ impl<I> Default for Config<I> {
             max_children: None,
             child_wrap: ChildWrap::Bottom,
             select_node: None,
+            select_marker: None,
+            right_column: None,
+            focus_context: false,
+            show_depth: false,
+            absolute_depth_base: None,
+            show_child_count: false,
+            show_zero_child_count: false,
+            single_child_straight: false,
+            glyph_set: GlyphSet::default(),
+            ascii: false,
+            invert_vertical: false,
+            show_hidden_summary: false,
+            show_hidden_children: false,
+            spacious: false,
+            label_gap: 1,
+            highlight_path: false,
+            highlight: false,
+            decorate: None,
+            show_root_parent: true,
+            max_nodes: None,
+            chain_ellipsis: false,
+            emit_anchors: false,
+            root_prefix: None,
+            layout: Layout::Vertical,
+            depth_gradient: None,
+            select_nodes_multi: vec![],
+            leading_newline: true,
         }
     }
 }
 
+impl<I> Config<I> {
+    /// Maximum number of children rendered per node.
+    pub fn with_max_children(mut self, max_children: u32) -> Self {
+        self.max_children = Some(max_children);
+        self
+    }
+
+    /// Side on which the children over `max_children` are hidden.
+    pub fn with_child_wrap(mut self, child_wrap: ChildWrap) -> Self {
+        self.child_wrap = child_wrap;
+        self
+    }
+
+    /// Render only `lvl` levels around the node `id`.
+    pub fn with_select_node(mut self, id: I, lvl: u32) -> Self {
+        self.select_node = Some((id, lvl));
+        self
+    }
+
+    /// Marker appended to the line of the selected node.
+    pub fn with_select_marker(mut self, select_marker: impl Into<String>) -> Self {
+        self.select_marker = Some(select_marker.into());
+        self
+    }
+
+    /// Value rendered flush-right on the line of each node.
+    pub fn with_right_column<N: 'static>(
+        mut self,
+        right_column: impl Fn(&N) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.right_column = Some(Arc::new(move |node: &dyn Any| {
+            node.downcast_ref::<N>().map(&right_column)
+        }));
+        self
+    }
+
+    /// With `select_node`, keep only the ancestors and collapse off-path children.
+    pub fn with_focus_context(mut self, focus_context: bool) -> Self {
+        self.focus_context = focus_context;
+        self
+    }
+
+    /// Append the depth of each node.
+    pub fn with_show_depth(mut self, show_depth: bool) -> Self {
+        self.show_depth = show_depth;
+        self
+    }
+
+    /// Offset added to the rendered depths.
+    pub fn with_absolute_depth_base(mut self, absolute_depth_base: u32) -> Self {
+        self.absolute_depth_base = Some(absolute_depth_base);
+        self
+    }
+
+    /// Append the number of direct children as `id[n]`.
+    pub fn with_show_child_count(mut self, show_child_count: bool) -> Self {
+        self.show_child_count = show_child_count;
+        self
+    }
+
+    /// With `show_child_count`, also render `[0]` on leaves.
+    pub fn with_show_zero_child_count(mut self, show_zero_child_count: bool) -> Self {
+        self.show_zero_child_count = show_zero_child_count;
+        self
+    }
+
+    /// Render an only child with a straight bar.
+    pub fn with_single_child_straight(mut self, single_child_straight: bool) -> Self {
+        self.single_child_straight = single_child_straight;
+        self
+    }
+
+    /// Glyphs used to draw the branches.
+    pub fn with_glyph_set(mut self, glyph_set: GlyphSet) -> Self {
+        self.glyph_set = glyph_set;
+        self
+    }
+
+    /// Draw the branches with `GlyphSet::ascii`.
+    pub fn with_ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Render the tree upside down.
+    pub fn with_invert_vertical(mut self, invert_vertical: bool) -> Self {
+        self.invert_vertical = invert_vertical;
+        self
+    }
+
+    /// Append the number of nodes hidden by `max_children`.
+    pub fn with_show_hidden_summary(mut self, show_hidden_summary: bool) -> Self {
+        self.show_hidden_summary = show_hidden_summary;
+        self
+    }
+
+    /// Render the number of children hidden by `max_children` as `… (+n more)`.
+    pub fn with_show_hidden_children(mut self, show_hidden_children: bool) -> Self {
+        self.show_hidden_children = show_hidden_children;
+        self
+    }
+
+    /// Separate the branches of the root with a blank line.
+    pub fn with_spacious(mut self, spacious: bool) -> Self {
+        self.spacious = spacious;
+        self
+    }
+
+    /// Number of spaces between the branch glyphs and the label of a node.
+    pub fn with_label_gap(mut self, label_gap: usize) -> Self {
+        self.label_gap = label_gap;
+        self
+    }
+
+    /// With `select_node`, trace the path to the selected node with heavy bars.
+    pub fn with_highlight_path(mut self, highlight_path: bool) -> Self {
+        self.highlight_path = highlight_path;
+        self
+    }
+
+    /// With `select_node`, color the selected node and its ancestors.
+    pub fn with_highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// (prefix, suffix) wrapped around the id of each node.
+    pub fn with_decorate<N: 'static>(
+        mut self,
+        decorate: impl Fn(&N) -> (String, String) + Send + Sync + 'static,
+    ) -> Self {
+        self.decorate = Some(Arc::new(move |node: &dyn Any| {
+            node.downcast_ref::<N>().map(&decorate)
+        }));
+        self
+    }
+
+    /// Render the parent of a subtree root.
+    pub fn with_show_root_parent(mut self, show_root_parent: bool) -> Self {
+        self.show_root_parent = show_root_parent;
+        self
+    }
+
+    /// Stop rendering after `max_nodes` nodes.
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    /// Replace runs of single-child nodes by an ellipsis.
+    pub fn with_chain_ellipsis(mut self, chain_ellipsis: bool) -> Self {
+        self.chain_ellipsis = chain_ellipsis;
+        self
+    }
+
+    /// Start each node line with `⟦id⟧`.
+    pub fn with_emit_anchors(mut self, emit_anchors: bool) -> Self {
+        self.emit_anchors = emit_anchors;
+        self
+    }
+
+    /// Prepended to the line of the root.
+    pub fn with_root_prefix(mut self, root_prefix: impl Into<String>) -> Self {
+        self.root_prefix = Some(root_prefix.into());
+        self
+    }
+
+    /// Direction in which the levels are laid out.
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Color the branch glyphs of each level, cycling through `palette`.
+    pub fn with_depth_gradient(mut self, palette: Vec<Color>) -> Self {
+        self.depth_gradient = Some(palette);
+        self
+    }
+
+    /// Render the paths from the root to each of `ids` with their children, collapsing the
+    /// other children along the paths. Takes precedence over `select_node`.
+    pub fn with_select_nodes_multi(mut self, ids: Vec<I>) -> Self {
+        self.select_nodes_multi = ids;
+        self
+    }
+
+    /// Start the output with a new line, turn off to start with the root line.
+    pub fn with_leading_newline(mut self, leading_newline: bool) -> Self {
+        self.leading_newline = leading_newline;
+        self
+    }
+}
+
 #[derive(Clone)]
 pub struct Meta<I> {
     select_nodes: Vec<I>,
+    // Nodes rendering only their children in `select_nodes`
+    collapse_ids: Vec<I>,
+    hidden_count: Cell<usize>,
 }
 
 impl<I> Default for Meta<I> {
     fn default() -> Self {
         Self {
             select_nodes: vec![],
+            collapse_ids: vec![],
+            hidden_count: Cell::new(0),
         }
     }
 }
 
-impl<I: Eq + PartialEq + Clone + Hash + Display + Ord, N: Node<I> + Clone> RootedTree<I, N> {
+impl<I: Eq + PartialEq + Clone + Hash + Display + Ord, N: Node<I> + Clone + 'static>
+    RootedTree<I, N>
+{
     pub fn report(&self, config: &Config<I>) -> Result<String> {
+        let mut out = String::new();
+        self.report_to(&mut out, config)?;
+        Ok(out)
+    }
+
+    /// Write the output of `report` into `w`, line by line.
+    ///
+    /// With `invert_vertical`, `emit_anchors` or `right_column`, the lines are held back until
+    /// the whole tree is rendered.
+    pub fn report_to<W: Write>(&self, w: &mut W, config: &Config<I>) -> Result<()> {
+        let mut out = ReportWriter::new(w, config);
+        self.report_view(config, |rooted_tree, meta| {
+            Self::_report(rooted_tree, config, meta, &mut out)
+        })?;
+        out.finish()
+    }
+
+    /// Split the output of `report` into pages of at most `lines_per_page` lines.
+    ///
+    /// The leading new line is dropped and every line of a page ends with a new line. The lines
+    /// of a node, with its multi-line id or the markers below it, are kept on the same page, on
+    /// a page of their own if they do not fit in `lines_per_page`.
+    pub fn report_paged(&self, config: &Config<I>, lines_per_page: usize) -> Result<Vec<String>> {
+        let mut sink = String::new();
+        let mut out = ReportWriter {
+            w: &mut sink,
+            buffer: Some(vec![]),
+            newline: false,
+        };
+        self.report_view(config, |rooted_tree, meta| {
+            Self::_report(rooted_tree, config, meta, &mut out)
+        })?;
+
+        let mut entries: Vec<Vec<&str>> = vec![];
+        let lines = out.buffer.take().unwrap_or_default();
+        for line in &lines {
+            match entries.last_mut() {
+                Some(entry) if line.joined => entry.extend(line.text.split('\n')),
+                _ => entries.push(line.text.split('\n').collect()),
+            }
+        }
+        let mut pages: Vec<String> = vec![];
+        let mut page_len = 0;
+        for entry in entries {
+            if pages.is_empty() || page_len + entry.len() > lines_per_page.max(1) {
+                pages.push(String::new());
+                page_len = 0;
+            }
+            if let Some(page) = pages.last_mut() {
+                for line in &entry {
+                    writeln!(page, "{}", line)?;
+                }
+            }
+            page_len += entry.len();
+        }
+        Ok(pages)
+    }
+
+    /// Render with the default config, without trailing whitespace on each line.
+    ///
+    /// Meant for snapshots that should not depend on padding details.
+    pub fn report_default(&self) -> String {
+        let out = self.report(&Config::default()).unwrap_or_default();
+        out.split('\n')
+            .map(|line| line.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Render only the path from the root down to `id`.
+    pub fn report_ancestors(&self, id: &I, config: &Config<I>) -> Result<String> {
+        let mut temp_rooted_tree = RootedTree::new();
+        let path = self.path_from_root(id).unwrap_or_default();
+        for (index, path_id) in path.iter().enumerate() {
+            if let Some(node) = self.get_node(path_id) {
+                let mut node = node.clone();
+                node.set_child_ids(path.get(index + 1).cloned().into_iter().collect());
+                if index == 0 {
+                    temp_rooted_tree.root_node = Some(node);
+                } else {
+                    temp_rooted_tree.child_nodes.insert(node.id(), node);
+                }
+            }
+        }
+        let mut sink = String::new();
+        let mut out = ReportWriter::new(&mut sink, config);
+        Self::_report(&temp_rooted_tree, config, &Meta::default(), &mut out)?;
+        out.finish()?;
+        Ok(sink)
+    }
+
+    // Call `f` with the tree to render and its meta, narrowed down by the node selection options
+    fn report_view<F>(&self, config: &Config<I>, f: F) -> Result<()>
+    where
+        F: FnOnce(&RootedTree<I, N>, &Meta<I>) -> Result<()>,
+    {
+        if !config.select_nodes_multi.is_empty() {
+            let (temp_rooted_tree, meta) = self.clone_multi_focus(&config.select_nodes_multi);
+            return f(&temp_rooted_tree, &meta);
+        }
         if let Some((node_id, lvl)) = &config.select_node {
-            let sub_lvl = lvl + 1 / 2;
-            let parent_ids = self.list_parent_ids_with_lvl(&node_id, Some(sub_lvl.clone()));
+            if config.focus_context {
+                if let Some(temp_rooted_tree) = self.clone_focus_context(node_id, *lvl) {
+                    let mut select_nodes = vec![node_id.clone()];
+                    select_nodes.extend(temp_rooted_tree.list_parent_ids(node_id));
+                    let meta = Meta {
+                        select_nodes,
+                        ..Meta::default()
+                    };
+                    return f(&temp_rooted_tree, &meta);
+                }
+            }
+            // Half of the levels are shown above the selected node
+            let sub_lvl = lvl.div_ceil(2);
+            let parent_ids = self.list_parent_ids_with_lvl(node_id, Some(sub_lvl));
             let root_id = parent_ids.last().unwrap_or(node_id);
-            if let Some(temp_rooted_tree) = self.clone_from_with_lvl(root_id.clone(), Some(sub_lvl))
+            // Without parents, the whole window is spent below the selected node
+            let down_lvl = if parent_ids.is_empty() { *lvl } else { sub_lvl };
+            if let Some(temp_rooted_tree) =
+                self.clone_from_with_lvl(root_id.clone(), Some(down_lvl))
             {
                 let mut select_nodes = vec![node_id.clone()];
                 select_nodes.extend(parent_ids);
-                let meta = Meta { select_nodes };
-                return Self::_report(&temp_rooted_tree, config, &meta);
+                let meta = Meta {
+                    select_nodes,
+                    ..Meta::default()
+                };
+                return f(&temp_rooted_tree, &meta);
+            }
+        }
+        f(self, &Meta::default())
+    }
+
+    // Copy of the nodes of `ids`, without links to the nodes left out
+    fn clone_only(&self, ids: &[I]) -> RootedTree<I, N> {
+        let mut temp_rooted_tree = RootedTree::new();
+        for (index, id) in ids.iter().enumerate() {
+            if let Some(node) = self.get_node(id) {
+                let mut node = node.clone();
+                node.set_child_ids(
+                    node.child_ids_vec()
+                        .into_iter()
+                        .filter(|child_id| {
+                            ids.contains(child_id) || self.get_node(child_id).is_none()
+                        })
+                        .collect(),
+                );
+                if index == 0 {
+                    temp_rooted_tree.root_node = Some(node);
+                } else {
+                    temp_rooted_tree.child_nodes.insert(node.id(), node);
+                }
+            }
+        }
+        temp_rooted_tree
+    }
+
+    // Paths from the root to each of `ids` with the children of `ids`, and the meta collapsing
+    // the off-path children of the ancestors
+    fn clone_multi_focus(&self, ids: &[I]) -> (RootedTree<I, N>, Meta<I>) {
+        let mut meta = Meta::default();
+        let mut kept_ids = vec![];
+        for id in ids {
+            let Some(path) = self.path_from_root(id) else {
+                continue;
+            };
+            meta.select_nodes.push(id.clone());
+            for path_id in &path[..path.len() - 1] {
+                if !meta.collapse_ids.contains(path_id) {
+                    meta.collapse_ids.push(path_id.clone());
+                }
+            }
+            kept_ids.extend(path);
+            kept_ids.extend(
+                self.get_node(id)
+                    .into_iter()
+                    .flat_map(|node| node.child_ids_vec()),
+            );
+        }
+        meta.collapse_ids
+            .retain(|id| !meta.select_nodes.contains(id));
+        let ancestor_ids = meta.collapse_ids.clone();
+        meta.select_nodes.extend(ancestor_ids);
+
+        let mut temp_rooted_tree = RootedTree::new();
+        temp_rooted_tree.root_node = self.root_node.clone();
+        for id in kept_ids {
+            if let Some(node) = self.child_nodes.get(&id) {
+                temp_rooted_tree.child_nodes.insert(id, node.clone());
+            }
+        }
+        (temp_rooted_tree, meta)
+    }
+
+    // Subtree of `id` up to `lvl` with all its ancestors up to the root
+    fn clone_focus_context(&self, id: &I, lvl: u32) -> Option<RootedTree<I, N>> {
+        self.get_node(id)?;
+        let mut temp_rooted_tree = self.clone_from_with_lvl(id.clone(), Some(lvl))?;
+        for parent_id in self.list_parent_ids(id) {
+            if let Some(parent_node) = self.get_node(&parent_id) {
+                if let Some(root_node) = temp_rooted_tree.root_node.take() {
+                    temp_rooted_tree
+                        .child_nodes
+                        .insert(root_node.id(), root_node);
+                }
+                temp_rooted_tree.root_node = Some(parent_node.clone());
             }
         }
-        Self::_report(self, config, &Meta::default())
+        Some(temp_rooted_tree)
     }
 
-    fn _report(
+    // Render the lines of `rooted_tree` into `out`, aligned if they are buffered
+    fn _report<W: Write>(
         rooted_tree: &RootedTree<I, N>,
         config: &Config<I>,
         meta: &Meta<I>,
-    ) -> Result<String> {
-        let mut out = String::new();
-        if let Some(root) = &rooted_tree.root_node {
-            if let (Some(_), len) = get_parent_id_and_len(root) {
-                write!(
-                    out,
-                    "\n{}{}",
-                    LvlChar::DashBar(0),
+        out: &mut ReportWriter<W>,
+    ) -> Result<()> {
+        let glyph_config;
+        let config = if config.ascii || config.invert_vertical {
+            let mut glyph_set = if config.ascii {
+                GlyphSet::ascii()
+            } else {
+                config.glyph_set.clone()
+            };
+            if config.invert_vertical {
+                glyph_set.elbow = glyph_set.top_elbow.clone();
+            }
+            glyph_config = Config {
+                glyph_set,
+                ..config.clone()
+            };
+            &glyph_config
+        } else {
+            config
+        };
+
+        let truncated_tree;
+        let (rooted_tree, truncated) = match (config.max_nodes, &rooted_tree.root_node) {
+            (Some(max_nodes), Some(root)) => {
+                let ids: Vec<I> = rooted_tree
+                    .bfs_from(&root.id())
+                    .map(|node| node.id())
+                    .take(max_nodes + 1)
+                    .collect();
+                if ids.len() > max_nodes {
+                    truncated_tree = rooted_tree.clone_only(&ids[..max_nodes]);
+                    (&truncated_tree, true)
+                } else {
+                    (rooted_tree, false)
+                }
+            }
+            _ => (rooted_tree, false),
+        };
+
+        if config.layout == Layout::Horizontal {
+            if let Some(root) = &rooted_tree.root_node {
+                let mut widths = vec![];
+                rooted_tree.column_widths(&root.id(), 0, &mut widths);
+                for row in rooted_tree.format_row(config, &root.id(), 0, &widths) {
+                    out.push(Line::new(format!("{}{}", config.glyph_set.space, row)))?;
+                }
+            }
+        } else if let Some(root) = &rooted_tree.root_node {
+            match get_parent_id_and_len(root) {
+                (Some(_), len) if config.show_root_parent => {
+                    out.push(Line::new(
+                        LvlChar::DashBar(0).display(&config.glyph_set).to_string(),
+                    ))?;
                     rooted_tree.format_node(
-                        &config,
+                        config,
                         root,
                         vec![LvlChar::DashBar(len)],
                         "".to_string(),
-                        meta
-                    )
-                )?;
-            } else {
-                write!(
-                    out,
-                    "{}",
-                    rooted_tree.format_node(&config, root, vec![], "".to_string(), meta)
-                )?;
+                        meta,
+                        out,
+                    )?;
+                }
+                _ => {
+                    rooted_tree.format_node(config, root, vec![], "".to_string(), meta, out)?;
+                }
             }
         }
-        write!(out, "\n")?;
-        Ok(out)
+        if let (true, Some(lines)) = (config.invert_vertical, out.buffer.as_mut()) {
+            lines.reverse();
+            // A line joined to the one above is now joined to the one below
+            let mut joined = false;
+            for line in lines.iter_mut() {
+                joined = std::mem::replace(&mut line.joined, joined);
+            }
+        }
+        if truncated {
+            out.push(Line::new("… truncated".to_string()))?;
+        }
+        if config.show_hidden_summary {
+            match meta.hidden_count.get() {
+                0 => {}
+                1 => out.push(Line::new("(1 node hidden)".to_string()))?,
+                count => out.push(Line::new(format!("({} nodes hidden)", count)))?,
+            }
+        }
+        if let Some(lines) = out.buffer.as_mut() {
+            if config.emit_anchors {
+                align_anchors(lines);
+            }
+            if config.right_column.is_some() {
+                align_right_column(lines);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn hidden_children_marker<I>(config: &Config<I>, hidden_len: usize) -> String {
+    if config.show_hidden_children {
+        format!(" … (+{} more)", hidden_len)
+    } else {
+        "".to_string()
+    }
+}
+
+// Width of `s` on a terminal, ANSI color sequences excluded
+fn visible_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut rest = s;
+    while let Some(start) = rest.find('\x1b') {
+        width += UnicodeWidthStr::width(&rest[..start]);
+        rest = &rest[start..];
+        rest = rest.find('m').map_or("", |end| &rest[end + 1..]);
+    }
+    width + UnicodeWidthStr::width(rest)
+}
+
+// A line of a report, its anchor and right column kept apart until they are aligned
+struct Line {
+    anchor: Option<String>,
+    text: String,
+    right_column: Option<String>,
+    // Kept on the same page as the line above, see `RootedTree::report_paged`
+    joined: bool,
+}
+
+impl Line {
+    fn new(text: String) -> Self {
+        Self {
+            anchor: None,
+            text,
+            right_column: None,
+            joined: false,
+        }
+    }
+
+    fn joined(text: String) -> Self {
+        Self {
+            joined: true,
+            ..Self::new(text)
+        }
+    }
+}
+
+// Lines of a report written to `w` as they come, or buffered until the end
+struct ReportWriter<'a, W: Write> {
+    w: &'a mut W,
+    buffer: Option<Vec<Line>>,
+    // Whether the next line starts with a new line, only false for the first one
+    newline: bool,
+}
+
+impl<'a, W: Write> ReportWriter<'a, W> {
+    fn new<I>(w: &'a mut W, config: &Config<I>) -> Self {
+        // Lines are only held back when they must be reordered or aligned
+        let buffered =
+            config.invert_vertical || config.emit_anchors || config.right_column.is_some();
+        Self {
+            w,
+            buffer: buffered.then(Vec::new),
+            newline: config.leading_newline,
+        }
+    }
+
+    // Write the buffered lines and the trailing new line
+    fn finish(mut self) -> Result<()> {
+        for line in self.buffer.take().unwrap_or_default() {
+            self.write_line(&line.text)?;
+        }
+        self.write_line("")
+    }
+
+    fn push(&mut self, line: Line) -> Result<()> {
+        match &mut self.buffer {
+            Some(lines) => lines.push(line),
+            None => self.write_line(&line.text)?,
+        }
+        Ok(())
+    }
+
+    fn write_line(&mut self, text: &str) -> Result<()> {
+        if self.newline {
+            self.w.write_char('\n')?;
+        }
+        self.newline = true;
+        self.w.write_str(text)?;
+        Ok(())
+    }
+}
+
+fn align_anchors(lines: &mut [Line]) {
+    let anchor_width = lines
+        .iter()
+        .filter_map(|line| line.anchor.as_deref())
+        .map(visible_width)
+        .max()
+        .unwrap_or(0);
+
+    for line in lines {
+        if let Some(anchor) = line.anchor.take() {
+            let padding = " ".repeat(anchor_width - visible_width(&anchor));
+            line.text.insert_str(0, &format!("{}{}", anchor, padding));
+        } else if !line.text.is_empty() {
+            line.text.insert_str(0, &" ".repeat(anchor_width));
+        }
+    }
+}
+
+fn align_right_column(lines: &mut [Line]) {
+    let left_width = lines
+        .iter()
+        .map(|line| visible_width(&line.text))
+        .max()
+        .unwrap_or(0);
+    let value_width = lines
+        .iter()
+        .filter_map(|line| line.right_column.as_deref())
+        .map(visible_width)
+        .max()
+        .unwrap_or(0);
+
+    for line in lines {
+        if let Some(value) = line.right_column.take() {
+            let padding =
+                left_width - visible_width(&line.text) + 1 + value_width - visible_width(&value);
+            line.text.push_str(&" ".repeat(padding));
+            line.text.push_str(&value);
+        }
     }
 }
 
@@ -123,49 +861,128 @@ fn get_parent_id_and_len<I: Display, N: Node<I>>(node: &N) -> (Option<I>, u32) {
     }
 }
 
-fn compute_prefixes(lvl_prefixes: &Vec<LvlChar>, suffix: String) -> String {
+fn compute_prefixes<I>(lvl_prefixes: &Vec<LvlChar>, suffix: String, config: &Config<I>) -> String {
     let mut result = String::new();
     if lvl_prefixes.is_empty() {
         result.push_str(suffix.as_str());
         return result;
     }
     for (index, lvl_prefix) in lvl_prefixes.iter().enumerate() {
-        if index == lvl_prefixes.len() - 1 {
-            result.push_str(suffix.as_str());
+        let glyphs = if index == lvl_prefixes.len() - 1 {
+            suffix.clone()
         } else {
-            result.push_str(lvl_prefix.to_string().as_str());
+            lvl_prefix.display(&config.glyph_set).to_string()
+        };
+        match &config.depth_gradient {
+            Some(palette) if !palette.is_empty() && !glyphs.is_empty() => {
+                let color = palette[index % palette.len()];
+                result.push_str(&format!("{}{}{}", color.ansi_start(), glyphs, COLOR_RESET));
+            }
+            _ => result.push_str(glyphs.as_str()),
         }
     }
     result
 }
 
-impl<I: Eq + PartialEq + Clone + Hash + Display, N: Node<I>> RootedTree<I, N> {
-    fn format_node(
+impl<I: Eq + PartialEq + Clone + Hash + Display, N: Node<I> + 'static> RootedTree<I, N> {
+    fn format_node<W: Write>(
         &self,
         config: &Config<I>,
         node: &N,
         lvl_prefixes: Vec<LvlChar>,
         suffix: String,
         meta: &Meta<I>,
-    ) -> String {
-        let prefix = compute_prefixes(&lvl_prefixes, suffix);
-        let mut result = format!("\n{} ", prefix);
+        out: &mut ReportWriter<W>,
+    ) -> Result<()> {
+        let is_root = self
+            .root_node
+            .as_ref()
+            .is_some_and(|root_node| root_node.id() == node.id());
+
+        let mut prefix = compute_prefixes(&lvl_prefixes, suffix, config);
+        if let (true, Some(root_prefix)) = (is_root, &config.root_prefix) {
+            prefix.insert_str(0, root_prefix);
+        }
+        // The leading space of a plain root line is not a gap after a branch glyph
+        let gap = if lvl_prefixes.is_empty() {
+            config.glyph_set.space.clone()
+        } else {
+            config.glyph_set.space.repeat(config.label_gap)
+        };
+        let mut result = format!("{}{}", prefix, gap);
+        let show_parent = !is_root || config.show_root_parent;
 
         let parent_len = if let (Some(parent_id), len) = get_parent_id_and_len(node) {
-            result.push_str(&format!("{} ↜ ", parent_id));
-            len
+            if show_parent {
+                result.push_str(&format!("{} ↜ ", parent_id));
+                len
+            } else {
+                0
+            }
         } else {
             0
         };
 
-        result.push_str(&format!("{}", node.id()));
+        let id = if config.highlight && meta.select_nodes.contains(&node.id()) {
+            format!("{}{}{}", HIGHLIGHT_START, node.id(), HIGHLIGHT_END)
+        } else {
+            format!("{}", node.id())
+        };
+        if let Some((prefix, suffix)) = config.decorate.as_ref().and_then(|decorate| decorate(node))
+        {
+            result.push_str(&format!("{}{}{}", prefix, id, suffix));
+        } else {
+            result.push_str(&id);
+        }
+
+        if config.show_child_count {
+            let child_count = node.child_ids_vec().len();
+            if child_count > 0 || config.show_zero_child_count {
+                result.push_str(&format!("[{}]", child_count));
+            }
+        }
+
+        if config.show_depth {
+            let depth = lvl_prefixes.len() as u32
+                - (self.is_subtree() && config.show_root_parent) as u32
+                + config.absolute_depth_base.unwrap_or(0);
+            result.push_str(&format!(" (lvl {})", depth));
+        }
+
+        if let Some(select_marker) = &config.select_marker {
+            if meta.select_nodes.first() == Some(&node.id()) {
+                result.push_str(select_marker);
+            }
+        }
+
+        out.push(Line {
+            anchor: config.emit_anchors.then(|| format!("⟦{}⟧", node.id())),
+            text: result,
+            right_column: config
+                .right_column
+                .as_ref()
+                .and_then(|right_column| right_column(node)),
+            joined: false,
+        })?;
 
         let mut vec_ids = node.child_ids_vec();
+
+        // Focus context
+        let mut collapsed_len = 0;
+        if (config.focus_context && meta.select_nodes.iter().skip(1).any(|id| id == &node.id()))
+            || meta.collapse_ids.contains(&node.id())
+        {
+            let len = vec_ids.len();
+            vec_ids.retain(|id| meta.select_nodes.contains(id));
+            collapsed_len = len - vec_ids.len();
+        }
+
         let mut vec_ids_len = vec_ids.len();
 
         // Wrap top
         if let Some(max_child) = config.max_children {
             if vec_ids_len > max_child as usize {
+                let len = vec_ids_len;
                 let add_wrap_top = if !meta.select_nodes.is_empty() {
                     let mut index_select_nodes = 0;
                     loop {
@@ -177,6 +994,7 @@ impl<I: Eq + PartialEq + Clone + Hash + Display, N: Node<I>> RootedTree<I, N> {
                             if index_node_stop_wrap == 0 {
                                 break false;
                             } else {
+                                self.hide_ids(&vec_ids[..index_node_stop_wrap], meta);
                                 vec_ids = vec_ids[index_node_stop_wrap..vec_ids_len].to_vec();
                                 vec_ids_len = vec_ids.len();
                                 break true;
@@ -190,6 +1008,7 @@ impl<I: Eq + PartialEq + Clone + Hash + Display, N: Node<I>> RootedTree<I, N> {
                     }
                 } else {
                     if let ChildWrap::Top = config.child_wrap {
+                        self.hide_ids(&vec_ids[..max_child as usize], meta);
                         vec_ids = vec_ids[max_child as usize..vec_ids_len].to_vec();
                         vec_ids_len = vec_ids.len();
                         true
@@ -202,61 +1021,242 @@ impl<I: Eq + PartialEq + Clone + Hash + Display, N: Node<I>> RootedTree<I, N> {
                     let mut lvl_prefixes = lvl_prefixes.clone();
                     lvl_prefixes.push(LvlChar::DashBar(parent_len));
                     lvl_prefixes.push(LvlChar::Empty);
-                    let prefix = compute_prefixes(&lvl_prefixes, "".to_string());
-                    result.push_str(&format!("\n{}", prefix));
+                    let prefix = compute_prefixes(&lvl_prefixes, "".to_string(), config);
+                    out.push(Line::joined(format!(
+                        "{}{}",
+                        prefix,
+                        hidden_children_marker(config, len - vec_ids_len)
+                    )))?;
                 }
             }
         }
 
+        // Children rendered before the next node on the selected path
+        let path_index = if config.highlight_path && meta.select_nodes.contains(&node.id()) {
+            vec_ids
+                .iter()
+                .position(|id| meta.select_nodes.contains(id))
+                .unwrap_or(0)
+        } else {
+            0
+        };
+
         for (index, child_id) in vec_ids.iter().enumerate() {
             let mut lvl_prefixes = lvl_prefixes.clone();
 
+            // Spacious
+            if config.spacious && is_root && index > 0 {
+                let mut lvl_prefixes = lvl_prefixes.clone();
+                lvl_prefixes.push(LvlChar::SolidBar(parent_len));
+                lvl_prefixes.push(LvlChar::Empty);
+                let prefix = compute_prefixes(&lvl_prefixes, "".to_string(), config);
+                out.push(Line::joined(prefix))?;
+            }
+
             // Wrap bottom
             if let Some(max_child) = config.max_children {
                 if ChildWrap::Bottom == config.child_wrap || config.select_node.is_some() {
                     if index == max_child as usize {
                         lvl_prefixes.push(LvlChar::DashBar(parent_len));
                         lvl_prefixes.push(LvlChar::Empty);
-                        let prefix = compute_prefixes(&lvl_prefixes, "".to_string());
-                        result.push_str(&format!("\n{}", prefix));
+                        let prefix = compute_prefixes(&lvl_prefixes, "".to_string(), config);
+                        out.push(Line::joined(format!(
+                            "{}{}",
+                            prefix,
+                            hidden_children_marker(config, vec_ids_len - index)
+                        )))?;
+                        self.hide_ids(&vec_ids[index..], meta);
                         break;
                     }
                 }
             }
 
-            let current_end_branch = if index == vec_ids_len - 1 {
+            // Chain ellipsis
+            let mut child_id = child_id.clone();
+            if config.chain_ellipsis {
+                let (end_id, skipped) = self.chain_end(&child_id);
+                if skipped > 1 {
+                    let mut lvl_prefixes = lvl_prefixes.clone();
+                    lvl_prefixes.push(LvlChar::Empty);
+                    let suffix = format!(
+                        "{}{}",
+                        config.glyph_set.space, config.glyph_set.vertical_ellipsis
+                    );
+                    let prefix = compute_prefixes(&lvl_prefixes, suffix, config);
+                    out.push(Line::new(prefix))?;
+                    child_id = end_id;
+                }
+            }
+
+            let current_end_branch = if index == vec_ids_len - 1 && collapsed_len == 0 {
                 lvl_prefixes.push(LvlChar::Space(parent_len));
                 true
+            } else if index < path_index {
+                lvl_prefixes.push(LvlChar::HeavyBar(parent_len));
+                false
             } else {
                 lvl_prefixes.push(LvlChar::SolidBar(parent_len));
                 false
             };
+            // Shift the descendants as much as the gap shifts the label
+            if config.label_gap > 1 {
+                if let Some(lvl_prefix) = lvl_prefixes.pop() {
+                    lvl_prefixes.push(LvlChar::Padded(Box::new(lvl_prefix), config.label_gap - 1));
+                }
+            }
 
             if let Some(child) = self.get_node(&child_id) {
-                let suffix = if current_end_branch {
-                    LvlChar::SolidAngle(parent_len).to_string()
+                let connector = if current_end_branch {
+                    if config.single_child_straight && vec_ids_len == 1 {
+                        LvlChar::SolidBar(parent_len)
+                    } else {
+                        LvlChar::SolidAngle(parent_len)
+                    }
+                } else {
+                    LvlChar::SolidCross(parent_len)
+                };
+                let suffix = if let Some(label) = child.edge_label() {
+                    // Shift the descendants as much as the label shifts the child
+                    if let Some(lvl_prefix) = lvl_prefixes.pop() {
+                        lvl_prefixes.push(LvlChar::Padded(
+                            Box::new(lvl_prefix),
+                            LvlChar::label_width(&label),
+                        ));
+                    }
+                    connector
+                        .display_labeled(&config.glyph_set, &label)
+                        .to_string()
                 } else {
-                    LvlChar::SolidCross(parent_len).to_string()
+                    connector.display(&config.glyph_set).to_string()
                 };
-                result.push_str(&self.format_node(
-                    &config,
-                    child,
-                    lvl_prefixes.clone(),
-                    suffix,
-                    meta,
-                ));
+                self.format_node(config, child, lvl_prefixes.clone(), suffix, meta, out)?;
             } else {
                 let suffix = if current_end_branch {
-                    LvlChar::SolidDashAngle(parent_len).to_string()
+                    LvlChar::SolidDashAngle(parent_len)
+                        .display(&config.glyph_set)
+                        .to_string()
                 } else {
-                    LvlChar::SolidDashCross(parent_len).to_string()
+                    LvlChar::SolidDashCross(parent_len)
+                        .display(&config.glyph_set)
+                        .to_string()
                 };
-                let prefix = compute_prefixes(&lvl_prefixes, suffix);
-                result.push_str(&format!("\n{} {}", prefix, child_id));
+                let prefix = compute_prefixes(&lvl_prefixes, suffix, config);
+                out.push(Line::new(format!(
+                    "{}{}{}",
+                    prefix,
+                    config.glyph_set.space.repeat(config.label_gap),
+                    child_id
+                )))?;
+            }
+        }
+
+        if collapsed_len > 0 {
+            let mut lvl_prefixes = lvl_prefixes.clone();
+            lvl_prefixes.push(LvlChar::Space(parent_len));
+            let suffix = LvlChar::SolidDashAngle(parent_len)
+                .display(&config.glyph_set)
+                .to_string();
+            let prefix = compute_prefixes(&lvl_prefixes, suffix, config);
+            out.push(Line::joined(format!(
+                "{}{}(+{})",
+                prefix,
+                config.glyph_set.space.repeat(config.label_gap),
+                collapsed_len
+            )))?;
+        }
+
+        Ok(())
+    }
+
+    // Last node of the run of single-child nodes starting at `id`, with the number of nodes
+    // before it in the run
+    fn chain_end(&self, id: &I) -> (I, usize) {
+        let mut id = id.clone();
+        let mut skipped = 0;
+        while let Some(node) = self.get_node(&id) {
+            match node.child_ids_vec().as_slice() {
+                [child_id] if self.get_node(child_id).is_some() => {
+                    id = child_id.clone();
+                    skipped += 1;
+                }
+                _ => break,
+            }
+        }
+        (id, skipped)
+    }
+
+    // Widest id of each level below `id`, for `Layout::Horizontal`
+    fn column_widths(&self, id: &I, lvl: usize, widths: &mut Vec<usize>) {
+        if widths.len() <= lvl {
+            widths.push(0);
+        }
+        let width = UnicodeWidthStr::width(id.to_string().as_str());
+        widths[lvl] = widths[lvl].max(width);
+        if let Some(node) = self.get_node(id) {
+            for child_id in node.child_ids_vec() {
+                self.column_widths(&child_id, lvl + 1, widths);
+            }
+        }
+    }
+
+    // Lines of the subtree of `id` laid out from left to right, the id on the first one
+    fn format_row(&self, config: &Config<I>, id: &I, lvl: usize, widths: &[usize]) -> Vec<String> {
+        let g = &config.glyph_set;
+        let label = id.to_string();
+        let child_ids = self
+            .get_node(id)
+            .map(|node| node.child_ids_vec())
+            .unwrap_or_default();
+        if child_ids.is_empty() {
+            return vec![label];
+        }
+
+        let padding = widths[lvl] - UnicodeWidthStr::width(label.as_str());
+        let mut rows = vec![];
+        for (index, child_id) in child_ids.iter().enumerate() {
+            let is_last = index == child_ids.len() - 1;
+            let (first, next) = match (child_ids.len(), index) {
+                (1, _) => (g.dash.clone(), g.space.clone()),
+                (_, 0) => (g.down_tee.clone(), g.vertical.clone()),
+                _ if is_last => (g.elbow.clone(), g.space.clone()),
+                _ => (g.tee.clone(), g.vertical.clone()),
+            };
+            // Branch glyphs are drawn over the 5 columns between a level and the next one
+            let (lead, first) = if index == 0 {
+                (
+                    format!("{}{}", label, g.space.repeat(padding)),
+                    format!("{}{}{}{}{}", g.space, g.dash, first, g.dash, g.space),
+                )
+            } else {
+                (
+                    g.space.repeat(widths[lvl]),
+                    format!("{}{}{}{}", g.space.repeat(2), first, g.dash, g.space),
+                )
+            };
+            let next = format!("{}{}{}", g.space.repeat(2), next, g.space.repeat(2));
+            for (row_index, row) in self
+                .format_row(config, child_id, lvl + 1, widths)
+                .into_iter()
+                .enumerate()
+            {
+                if row_index == 0 {
+                    rows.push(format!("{}{}{}", lead, first, row));
+                } else {
+                    rows.push(format!("{}{}{}", g.space.repeat(widths[lvl]), next, row));
+                }
             }
         }
+        rows
+    }
 
-        result
+    // Count the nodes hidden with their descendants
+    fn hide_ids(&self, ids: &[I], meta: &Meta<I>) {
+        let count = ids
+            .iter()
+            .flat_map(|id| std::iter::once(id.clone()).chain(self.list_child_ids(id)))
+            .filter(|id| self.get_node(id).is_some())
+            .count();
+        meta.hidden_count.set(meta.hidden_count.get() + count);
     }
 }
 
@@ -354,6 +1354,46 @@ mod tests {
         println!("{}", tree.report(&config).unwrap());
     }
 
+    #[test]
+    fn select_node_lvl_parent_context() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        for id in 2..=7 {
+            tree.add_node(Some(id - 1), DataNode::new(id)).unwrap();
+        }
+
+        let config = Config {
+            select_node: Some((5, 2)),
+            ..Config::default()
+        };
+        let report_lvl_2 = tree.report(&config).unwrap();
+        assert_eq!(
+            report_lvl_2,
+            "
+ ╎  
+ 3 ↜ 4
+ ╎   └── 4 ↜ 5
+ ╎       └╌╌╌╌╌╌ 6
+"
+        );
+
+        let config = Config {
+            select_node: Some((5, 3)),
+            ..Config::default()
+        };
+        let report_lvl_3 = tree.report(&config).unwrap();
+        assert_eq!(
+            report_lvl_3,
+            "
+ ╎  
+ 2 ↜ 3
+ ╎   └── 3 ↜ 4
+ ╎       └── 4 ↜ 5
+ ╎           └╌╌╌╌╌╌ 6
+"
+        );
+    }
+
     #[test]
     fn select_node_lvl_challenge() {
         let mut tree = RootedTree::new();
@@ -380,6 +1420,984 @@ mod tests {
         println!("{}", tree.report(&config).unwrap());
     }
 
+    #[test]
+    fn select_node_root() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(3), DataNode::new(4)).unwrap();
+        tree.add_node(Some(4), DataNode::new(5)).unwrap();
+
+        let config = Config::default().with_select_node(1, 0);
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ ├╌╌╌╌╌╌ 2
+ └╌╌╌╌╌╌ 3
+"
+        );
+
+        let config = Config::default().with_select_node(1, 2);
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ ├── 1 ↜ 2
+ └── 1 ↜ 3
+     └── 3 ↜ 4
+         └╌╌╌╌╌╌ 5
+"
+        );
+
+        let config = Config::default().with_select_node(1, 3);
+        assert_eq!(tree.report(&config).unwrap(), tree.report_default());
+    }
+
+    #[test]
+    fn select_node_marker() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(3), DataNode::new(4)).unwrap();
+
+        let config = Config {
+            select_node: Some((3, 1)),
+            select_marker: Some(" ◀".to_string()),
+            ..Config::default()
+        };
+        let report = tree.report(&config).unwrap();
+
+        let marked_lines: Vec<&str> = report.lines().filter(|l| l.contains('◀')).collect();
+        assert_eq!(marked_lines, vec![" └── 1 ↜ 3 ◀"]);
+    }
+
+    #[test]
+    fn right_column() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(1), DataNode::new(4)).unwrap();
+
+        for id in 1..=4 {
+            tree.get_mut_node(&id).unwrap().value = id * 10;
+        }
+
+        let config =
+            Config::default().with_right_column(|node: &DataNode| format!("{}kb", node.value));
+        let report = tree.report(&config).unwrap();
+        assert_eq!(
+            report,
+            "
+ 1             10kb
+ ├── 1 ↜ 2     20kb
+ │   └── 2 ↜ 3 30kb
+ └── 1 ↜ 4     40kb
+"
+        );
+
+        // Nodes of another type have no value
+        let config = Config::default().with_right_column(|node: &PathNode| node.id.clone());
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            tree.report(&Config::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn right_column_control_chars() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+
+        let config = Config::default()
+            .with_emit_anchors(true)
+            .with_right_column(|node: &DataNode| format!("\u{1e}{}\u{1f}", node.id));
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+⟦1⟧ 1         \u{1e}1\u{1f}
+⟦2⟧ └── 1 ↜ 2 \u{1e}2\u{1f}
+"
+        );
+    }
+
+    #[test]
+    fn config_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Config<i32>>();
+    }
+
+    #[test]
+    fn select_node_focus_context() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(1), DataNode::new(4)).unwrap();
+        tree.add_node(Some(2), DataNode::new(5)).unwrap();
+        tree.add_node(Some(2), DataNode::new(6)).unwrap();
+        tree.add_node(Some(6), DataNode::new(7)).unwrap();
+        tree.add_node(Some(7), DataNode::new(8)).unwrap();
+        tree.add_node(Some(7), DataNode::new(9)).unwrap();
+
+        let config = Config {
+            select_node: Some((7, 1)),
+            focus_context: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ ├── 1 ↜ 2
+ │   ├── 2 ↜ 6
+ │   │   └── 6 ↜ 7
+ │   │       ├── 7 ↜ 8
+ │   │       └── 7 ↜ 9
+ │   └╌╌╌╌╌╌ (+1)
+ └╌╌╌╌╌╌ (+2)
+"
+        );
+    }
+
+    #[test]
+    fn select_nodes_multi() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(1), DataNode::new(4)).unwrap();
+        tree.add_node(Some(2), DataNode::new(5)).unwrap();
+        tree.add_node(Some(2), DataNode::new(6)).unwrap();
+        tree.add_node(Some(6), DataNode::new(7)).unwrap();
+        tree.add_node(Some(4), DataNode::new(8)).unwrap();
+        tree.add_node(Some(4), DataNode::new(9)).unwrap();
+        tree.add_node(Some(9), DataNode::new(10)).unwrap();
+        tree.add_node(Some(10), DataNode::new(11)).unwrap();
+
+        let config = Config::default().with_select_nodes_multi(vec![6, 9]);
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ ├── 1 ↜ 2
+ │   ├── 2 ↜ 6
+ │   │   └── 6 ↜ 7
+ │   └╌╌╌╌╌╌ (+1)
+ ├── 1 ↜ 4
+ │   ├── 4 ↜ 9
+ │   │   └── 9 ↜ 10
+ │   │       └╌╌╌╌╌╌ 11
+ │   └╌╌╌╌╌╌ (+1)
+ └╌╌╌╌╌╌ (+1)
+"
+        );
+    }
+
+    #[test]
+    fn show_depth() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        let config = Config {
+            show_depth: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1 (lvl 0)
+ └── 1 ↜ 2 (lvl 1)
+     └── 2 ↜ 3 (lvl 2)
+"
+        );
+    }
+
+    #[test]
+    fn show_depth_with_absolute_depth_base() {
+        let mut tree = RootedTree::new();
+        let mut node = DataNode::new(1);
+        node.set_parent_id(0);
+        tree.set_root_node(node);
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+
+        let config = Config {
+            show_depth: true,
+            absolute_depth_base: Some(3),
+            ..Config::default()
+        };
+        let report = tree.report(&config).unwrap();
+        let depths: Vec<&str> = report
+            .lines()
+            .filter_map(|line| line.split_once("(lvl "))
+            .map(|(_, depth)| depth)
+            .collect();
+        assert_eq!(depths, vec!["3)", "4)"]);
+    }
+
+    #[test]
+    fn show_child_count() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+
+        let config = Config {
+            show_child_count: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1[2]
+ ├── 1 ↜ 2[1]
+ │   └── 2 ↜ 4
+ └── 1 ↜ 3
+"
+        );
+
+        let config = Config {
+            show_child_count: true,
+            show_zero_child_count: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1[2]
+ ├── 1 ↜ 2[1]
+ │   └── 2 ↜ 4[0]
+ └── 1 ↜ 3[0]
+"
+        );
+    }
+
+    #[test]
+    fn single_child_straight() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+
+        let config = Config {
+            single_child_straight: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ │   1 ↜ 2
+     ├── 2 ↜ 3
+     └── 2 ↜ 4
+"
+        );
+    }
+
+    #[test]
+    fn ascii() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        let mut node = DataNode::new(2);
+        node.add_child_id(4);
+        tree.add_node(Some(1), node).unwrap();
+        tree.add_node(Some(2), DataNode::new(5)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+
+        let config = Config {
+            ascii: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ +-- 1 ↜ 2
+ |   +...... 4
+ |   `-- 2 ↜ 5
+ `-- 1 ↜ 3
+"
+        );
+    }
+
+    #[test]
+    fn invert_vertical() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(1), DataNode::new(5)).unwrap();
+
+        assert_eq!(
+            tree.report(&Config::default()).unwrap(),
+            "
+ 1
+ ├── 1 ↜ 2
+ │   ├── 2 ↜ 3
+ │   └── 2 ↜ 4
+ └── 1 ↜ 5
+"
+        );
+        let config = Config {
+            invert_vertical: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ ┌── 1 ↜ 5
+ │   ┌── 2 ↜ 4
+ │   ├── 2 ↜ 3
+ ├── 1 ↜ 2
+ 1
+"
+        );
+    }
+
+    #[test]
+    fn highlight() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(1), DataNode::new(5)).unwrap();
+
+        let config = Config {
+            select_node: Some((3, 4)),
+            highlight: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ \x1b[1;33m1\x1b[0m
+ ├── 1 ↜ \x1b[1;33m2\x1b[0m
+ │   ├── 2 ↜ \x1b[1;33m3\x1b[0m
+ │   └── 2 ↜ 4
+ └── 1 ↜ 5
+"
+        );
+    }
+
+    #[test]
+    fn depth_gradient() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(1), DataNode::new(4)).unwrap();
+
+        let config = Config {
+            depth_gradient: Some(vec![Color::Red, Color::Green]),
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+\x1b[31m ├──\x1b[0m 1 ↜ 2
+\x1b[31m │  \x1b[0m\x1b[32m └──\x1b[0m 2 ↜ 3
+\x1b[31m └──\x1b[0m 1 ↜ 4
+"
+        );
+
+        // Widths ignore the color sequences
+        let config = config.with_right_column(|node: &DataNode| node.id.to_string());
+        let plain_config =
+            Config::default().with_right_column(|node: &DataNode| node.id.to_string());
+        let out = tree.report(&config).unwrap();
+        let plain_out = tree.report(&plain_config).unwrap();
+        let widths = |out: &str| out.lines().map(visible_width).collect::<Vec<_>>();
+        assert_eq!(widths(&out), widths(&plain_out));
+    }
+
+    #[test]
+    fn custom_glyph_set() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        let mut node = DataNode::new(2);
+        node.add_child_id(4);
+        tree.add_node(Some(1), node).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+
+        let config = Config {
+            glyph_set: GlyphSet {
+                vertical: "!".to_string(),
+                heavy_vertical: "#".to_string(),
+                tee: "T".to_string(),
+                elbow: "L".to_string(),
+                down_tee: "Y".to_string(),
+                top_elbow: "F".to_string(),
+                dash: "=".to_string(),
+                dashed_vertical: "?".to_string(),
+                dashed_dash: "~".to_string(),
+                space: ".".to_string(),
+                vertical_ellipsis: "%".to_string(),
+            },
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+.1
+.T==.1 ↜ 2
+.!...L~~~~~~.4
+.L==.1 ↜ 3
+"
+        );
+    }
+
+    #[test]
+    fn show_hidden_summary() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(1), DataNode::new(4)).unwrap();
+        tree.add_node(Some(2), DataNode::new(5)).unwrap();
+        tree.add_node(Some(2), DataNode::new(6)).unwrap();
+        tree.add_node(Some(3), DataNode::new(7)).unwrap();
+        tree.add_node(Some(7), DataNode::new(8)).unwrap();
+        tree.add_node(Some(4), DataNode::new(9)).unwrap();
+
+        let config = Config {
+            max_children: Some(1),
+            show_hidden_summary: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ ├── 1 ↜ 2
+ │   ├── 2 ↜ 5
+ │   ╎  
+ ╎  
+(6 nodes hidden)
+"
+        );
+
+        let mut node = DataNode::new(10);
+        node.add_child_id(11);
+        tree.add_node(Some(4), node).unwrap();
+        tree.get_mut_node(&1).unwrap().add_child_id(12);
+        assert!(tree
+            .report(&config)
+            .unwrap()
+            .ends_with("(7 nodes hidden)\n"));
+    }
+
+    #[test]
+    fn spacious() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(1), DataNode::new(5)).unwrap();
+        tree.add_node(Some(1), DataNode::new(6)).unwrap();
+
+        let config = Config {
+            spacious: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ ├── 1 ↜ 2
+ │   ├── 2 ↜ 3
+ │   └── 2 ↜ 4
+ │  
+ ├── 1 ↜ 5
+ │  
+ └── 1 ↜ 6
+"
+        );
+    }
+
+    #[test]
+    fn max_nodes() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(1), DataNode::new(5)).unwrap();
+        tree.add_node(Some(5), DataNode::new(6)).unwrap();
+        tree.add_node(Some(6), DataNode::new(7)).unwrap();
+
+        let config = Config {
+            max_nodes: Some(5),
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ ├── 1 ↜ 2
+ │   ├── 2 ↜ 3
+ │   └── 2 ↜ 4
+ └── 1 ↜ 5
+… truncated
+"
+        );
+
+        let config = Config {
+            max_nodes: Some(7),
+            ..Config::default()
+        };
+        assert!(!tree.report(&config).unwrap().contains("truncated"));
+    }
+
+    #[test]
+    fn chain_ellipsis() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        for id in 2..=10 {
+            tree.add_node(Some(id - 1), DataNode::new(id)).unwrap();
+        }
+
+        let config = Config {
+            chain_ellipsis: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ ⋮
+ └── 9 ↜ 10
+"
+        );
+
+        tree.add_node(Some(1), DataNode::new(11)).unwrap();
+        tree.add_node(Some(11), DataNode::new(12)).unwrap();
+        tree.add_node(Some(10), DataNode::new(13)).unwrap();
+        tree.add_node(Some(10), DataNode::new(14)).unwrap();
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ ⋮
+ ├── 9 ↜ 10
+ │   ├── 10 ↜ 13
+ │   └── 10 ↜ 14
+ └── 1 ↜ 11
+     └── 11 ↜ 12
+"
+        );
+    }
+
+    #[test]
+    fn emit_anchors() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(30)).unwrap();
+        let mut node = DataNode::new(4);
+        node.add_child_id(5);
+        tree.add_node(Some(1), node).unwrap();
+
+        let config = Config {
+            emit_anchors: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+⟦1⟧  1
+⟦2⟧  ├── 1 ↜ 2
+⟦30⟧ │   └── 2 ↜ 30
+⟦4⟧  └── 1 ↜ 4
+         └╌╌╌╌╌╌ 5
+"
+        );
+    }
+
+    #[test]
+    fn root_prefix() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        let config = Config {
+            root_prefix: Some("●".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+● 1
+ └── 1 ↜ 2
+     └── 2 ↜ 3
+"
+        );
+    }
+
+    #[test]
+    fn layout_horizontal() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(1), DataNode::new(40)).unwrap();
+        tree.add_node(Some(2), DataNode::new(5)).unwrap();
+        tree.add_node(Some(2), DataNode::new(6)).unwrap();
+        tree.add_node(Some(40), DataNode::new(7)).unwrap();
+
+        let config = Config {
+            layout: Layout::Horizontal,
+            ..Config::default()
+        };
+        let out = tree.report(&config).unwrap();
+        assert_eq!(
+            out,
+            "
+ 1 ─┬─ 2  ─┬─ 5
+    │      └─ 6
+    ├─ 3
+    └─ 40 ─── 7
+"
+        );
+        // The root is the only id in the leftmost column
+        let lines: Vec<&str> = out.lines().skip(1).collect();
+        assert!(lines[0].starts_with(" 1"));
+        assert!(lines[1..].iter().all(|line| line.starts_with("  ")));
+    }
+
+    #[test]
+    fn report_to() {
+        let mut tree = RootedTree::new();
+        for id in 1..=6 {
+            tree.add_node((id > 1).then_some(id / 2), DataNode::new(id))
+                .unwrap();
+        }
+
+        for config in [
+            Config::default(),
+            Config::default().with_select_node(5, 2),
+            Config::default().with_max_children(1),
+        ] {
+            let mut out = String::new();
+            tree.report_to(&mut out, &config).unwrap();
+            assert_eq!(out, tree.report(&config).unwrap());
+        }
+
+        // The lines are written as they are rendered
+        struct Chunks(Vec<String>);
+        impl Write for Chunks {
+            fn write_str(&mut self, s: &str) -> std::fmt::Result {
+                self.0.push(s.to_string());
+                Ok(())
+            }
+        }
+        let mut chunks = Chunks(vec![]);
+        tree.report_to(&mut chunks, &Config::default()).unwrap();
+        assert_eq!(chunks.0.concat(), tree.report(&Config::default()).unwrap());
+        assert!(chunks
+            .0
+            .iter()
+            .all(|chunk| chunk.matches('\n').count() <= 1));
+    }
+
+    #[test]
+    fn leading_newline() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+
+        let config = Config::default().with_leading_newline(false);
+        let out = tree.report(&config).unwrap();
+        assert!(out.starts_with(" 1"));
+        assert_eq!(out, " 1\n └── 1 ↜ 2\n");
+        assert_eq!(out, format!("{}", tree));
+
+        let sub_tree = tree.clone_from(2).unwrap();
+        assert_eq!(sub_tree.report(&config).unwrap(), " ╎  \n 1 ↜ 2\n");
+    }
+
+    #[test]
+    fn report_paged() {
+        let mut tree = RootedTree::new();
+        for id in 1..=7 {
+            tree.add_node((id > 1).then_some(id / 2), DataNode::new(id))
+                .unwrap();
+        }
+
+        let pages = tree.report_paged(&Config::default(), 3).unwrap();
+        assert_eq!(pages.len(), 3);
+        assert!(pages.iter().all(|page| page.lines().count() <= 3));
+        assert_eq!(
+            pages,
+            vec![
+                " 1\n ├── 1 ↜ 2\n │   ├── 2 ↜ 4\n",
+                " │   └── 2 ↜ 5\n └── 1 ↜ 3\n     ├── 3 ↜ 6\n",
+                "     └── 3 ↜ 7\n",
+            ]
+        );
+        assert_eq!(
+            format!("\n{}", pages.concat()),
+            tree.report(&Config::default()).unwrap()
+        );
+
+        assert!(RootedTree::<i32, DataNode>::new()
+            .report_paged(&Config::default(), 3)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn report_paged_multi_line_id() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, PathNode::new("r")).unwrap();
+        tree.add_node(Some("r".to_string()), PathNode::new("x"))
+            .unwrap();
+        tree.add_node(Some("r".to_string()), PathNode::new("multi\nline"))
+            .unwrap();
+        tree.add_node(Some("r".to_string()), PathNode::new("y"))
+            .unwrap();
+
+        // The two lines of "multi\nline" would straddle the first page boundary
+        let pages = tree.report_paged(&Config::default(), 3).unwrap();
+        assert_eq!(
+            pages,
+            vec![" r\n ├── r ↜ x\n", " ├── r ↜ multi\nline\n └── r ↜ y\n"]
+        );
+        assert_eq!(
+            format!("\n{}", pages.concat()),
+            tree.report(&Config::default()).unwrap()
+        );
+
+        // An entry longer than a page gets a page of its own
+        let pages = tree.report_paged(&Config::default(), 1).unwrap();
+        assert_eq!(pages.len(), 4);
+        assert_eq!(pages[2], " ├── r ↜ multi\nline\n");
+    }
+
+    #[test]
+    fn report_ancestors() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(3), DataNode::new(4)).unwrap();
+        tree.add_node(Some(3), DataNode::new(5)).unwrap();
+        tree.add_node(Some(5), DataNode::new(6)).unwrap();
+        tree.add_node(Some(6), DataNode::new(7)).unwrap();
+
+        assert_eq!(
+            tree.report_ancestors(&6, &Config::default()).unwrap(),
+            "
+ 1
+ └── 1 ↜ 3
+     └── 3 ↜ 5
+         └── 5 ↜ 6
+"
+        );
+        assert_eq!(tree.report_ancestors(&8, &Config::default()).unwrap(), "\n");
+    }
+
+    #[test]
+    fn highlight_path() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(1), DataNode::new(4)).unwrap();
+        tree.add_node(Some(4), DataNode::new(5)).unwrap();
+        tree.add_node(Some(4), DataNode::new(6)).unwrap();
+        tree.add_node(Some(4), DataNode::new(7)).unwrap();
+        tree.add_node(Some(1), DataNode::new(8)).unwrap();
+
+        let config = Config {
+            select_node: Some((6, 4)),
+            highlight_path: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ ├── 1 ↜ 2
+ ┃   └── 2 ↜ 3
+ ├── 1 ↜ 4
+ │   ├── 4 ↜ 5
+ │   ├── 4 ↜ 6
+ │   └── 4 ↜ 7
+ └── 1 ↜ 8
+"
+        );
+    }
+
+    #[test]
+    fn decorate() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(22)).unwrap();
+        tree.add_node(Some(22), DataNode::new(3)).unwrap();
+        tree.add_node(Some(22), DataNode::new(4)).unwrap();
+        tree.get_mut_node(&22).unwrap().value = 1;
+        tree.get_mut_node(&4).unwrap().value = 1;
+
+        // Only the nodes with a value are wrapped
+        let config = Config::default()
+            .with_decorate(|node: &DataNode| {
+                if node.value > 0 {
+                    ("«".to_string(), "»".to_string())
+                } else {
+                    ("".to_string(), "".to_string())
+                }
+            })
+            .with_right_column(|node: &DataNode| node.id.to_string());
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1                 1
+ └── 1 ↜ «22»     22
+     ├── 22 ↜ 3    3
+     └── 22 ↜ «4»  4
+"
+        );
+    }
+
+    #[test]
+    fn hide_root_parent() {
+        let mut tree = RootedTree::new();
+        let mut node = DataNode::new(1);
+        node.set_parent_id(2222);
+        tree.set_root_node(node);
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        let config = Config {
+            show_root_parent: false,
+            show_depth: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1 (lvl 0)
+ └── 1 ↜ 2 (lvl 1)
+     └── 2 ↜ 3 (lvl 2)
+"
+        );
+    }
+
+    #[test]
+    fn edge_label() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        let mut node = DataNode::new(2);
+        node.edge_label = Some("is".to_string());
+        tree.add_node(Some(1), node).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(1), DataNode::new(5)).unwrap();
+
+        assert_eq!(
+            tree.report(&Config::default()).unwrap(),
+            "
+ 1
+ ├──(is)── 1 ↜ 2
+ │         ├── 2 ↜ 3
+ │         └── 2 ↜ 4
+ └── 1 ↜ 5
+"
+        );
+    }
+
+    #[test]
+    fn show_hidden_children() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        for id in 2..=6 {
+            tree.add_node(Some(1), DataNode::new(id)).unwrap();
+        }
+
+        let config = Config {
+            max_children: Some(2),
+            show_hidden_children: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ ├── 1 ↜ 2
+ ├── 1 ↜ 3
+ ╎   … (+3 more)
+"
+        );
+
+        let config = Config {
+            child_wrap: ChildWrap::Top,
+            ..config
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ ╎   … (+2 more)
+ ├── 1 ↜ 4
+ ├── 1 ↜ 5
+ └── 1 ↜ 6
+"
+        );
+    }
+
+    #[test]
+    fn label_gap() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        let mut node = DataNode::new(3);
+        node.add_child_id(4);
+        tree.add_node(Some(2), node).unwrap();
+        tree.add_node(Some(2), DataNode::new(5)).unwrap();
+        tree.add_node(Some(1), DataNode::new(6)).unwrap();
+
+        let config = Config {
+            label_gap: 3,
+            ..Config::default()
+        };
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ ├──   1 ↜ 2
+ │     ├──   2 ↜ 3
+ │     │     └╌╌╌╌╌╌   4
+ │     └──   2 ↜ 5
+ └──   1 ↜ 6
+"
+        );
+
+        let mut tree = RootedTree::new();
+        let mut node = DataNode::new(1);
+        node.add_child_id(2);
+        tree.add_node(None, node).unwrap();
+        assert_eq!(
+            tree.report(&config).unwrap(),
+            "
+ 1
+ └╌╌╌╌╌╌   2
+"
+        );
+    }
+
     #[test]
     fn max_child_1_lvl() {
         let mut tree = RootedTree::new();
@@ -523,17 +2541,14 @@ mod tests {
         node.set_parent_id(1);
         tree.set_child_node(node).unwrap();
 
-        println!("{}", tree.report(&Config::default()).unwrap());
-        //        assert_eq!(
-        //            format!("{:?}", tree),
-        //            format!(
-        //                "
-        // 1
-        // ├── 1 ↜ 2
-        // └── 1 ↜ 3
-        // "
-        //            )
-        //        );
+        assert_eq!(
+            tree.report_default(),
+            "
+ 1
+ ├── 1 ↜ 2
+ └── 1 ↜ 3
+"
+        );
     }
 
     #[test]
@@ -545,7 +2560,16 @@ mod tests {
         tree.add_node(Some(3), DataNode::new(4)).unwrap();
         tree.add_node(Some(4), DataNode::new(5)).unwrap();
 
-        println!("{}", tree.report(&Config::default()).unwrap());
+        assert_eq!(
+            tree.report_default(),
+            "
+ 1
+ └── 1 ↜ 2
+     └── 2 ↜ 3
+         └── 3 ↜ 4
+             └── 4 ↜ 5
+"
+        );
     }
 
     #[test]
@@ -1,6 +1,7 @@
 use crate::node::Node;
 use crate::{Error, Result};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Debug, Display};
 use std::hash::Hash;
 
 pub struct RootedTree<I, N: Node<I>> {
@@ -37,6 +38,16 @@ impl<I: Eq + PartialEq + Clone + Hash, N: Node<I>> RootedTree<I, N> {
         Ok(())
     }
 
+    /// Root node, `None` on an empty tree.
+    pub fn root(&self) -> Option<&N> {
+        self.root_node.as_ref()
+    }
+
+    /// Id of the root node, `None` on an empty tree.
+    pub fn root_id(&self) -> Option<I> {
+        self.root_node.as_ref().map(|root_node| root_node.id())
+    }
+
     pub fn get_node(&self, id: &I) -> Option<&N> {
         if let Some(node) = self.child_nodes.get(id) {
             Some(node)
@@ -51,6 +62,15 @@ impl<I: Eq + PartialEq + Clone + Hash, N: Node<I>> RootedTree<I, N> {
         }
     }
 
+    /// Whether `id` is the root or one of the child nodes, missing child ids excluded.
+    pub fn contains(&self, id: &I) -> bool {
+        self.child_nodes.contains_key(id)
+            || self
+                .root_node
+                .as_ref()
+                .is_some_and(|root_node| root_node.id() == *id)
+    }
+
     pub fn get_mut_node(&mut self, id: &I) -> Option<&mut N> {
         if let Some(node) = self.child_nodes.get_mut(id) {
             Some(node)
@@ -65,6 +85,53 @@ impl<I: Eq + PartialEq + Clone + Hash, N: Node<I>> RootedTree<I, N> {
         }
     }
 
+    /// Parent id of `id`, `None` for the root or a missing node.
+    ///
+    /// The root of a subtree returns the id of its parent, which is not in the tree.
+    pub fn parent_id(&self, id: &I) -> Option<I> {
+        self.get_node(id)?.parent_id()
+    }
+
+    /// Position of `id` among the children of its parent, `None` for the root or a missing node.
+    pub fn sibling_index(&self, id: &I) -> Option<usize> {
+        let parent_node = self.get_node(&self.parent_id(id)?)?;
+        parent_node
+            .child_ids_vec()
+            .iter()
+            .position(|child_id| child_id == id)
+    }
+
+    /// Ids of the other children of the parent of `id`, empty for the root or a missing node.
+    pub fn siblings(&self, id: &I) -> Vec<I> {
+        if let Some(parent_node) = self.parent_id(id).and_then(|id| self.get_node(&id)) {
+            parent_node
+                .child_ids_vec()
+                .into_iter()
+                .filter(|child_id| child_id != id)
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+
+    /// Child ids listed by both `a` and `b`, in the order of `a`, empty if one of them is missing.
+    pub fn common_children(&self, a: &I, b: &I) -> Vec<I> {
+        match (self.get_node(a), self.get_node(b)) {
+            (Some(node_a), Some(node_b)) => {
+                let child_ids_b = node_b.child_ids_vec();
+                node_a
+                    .child_ids_vec()
+                    .into_iter()
+                    .filter(|child_id| child_ids_b.contains(child_id))
+                    .collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Remove the node `id` alone and return it, see `remove_subtree` to drop its descendants.
+    ///
+    /// Removing the root empties the tree.
     pub fn remove_node(&mut self, id: &I) -> Option<N> {
         if let Some(node) = self.child_nodes.remove(id) {
             if let Some(parent_id) = node.parent_id() {
@@ -73,21 +140,277 @@ impl<I: Eq + PartialEq + Clone + Hash, N: Node<I>> RootedTree<I, N> {
                 }
             }
             Some(node)
-        } else {
+        } else if self.root_node.as_ref().is_some_and(|node| node.id() == *id) {
+            // Child nodes can not be kept without a root
+            self.child_nodes.clear();
             self.root_node.take()
+        } else {
+            None
         }
     }
 
-    pub fn len(&self) -> usize {
-        if let Some(_) = &self.root_node {
-            self.child_nodes.len() + 1
-        } else if self.child_nodes.len() > 0 {
-            unreachable!("Rooted tree could not have child nodes without a root node")
+    /// Reparent `id` under `parent`, updating the links on both sides.
+    ///
+    /// With `None`, `id` becomes the root and the former root is demoted as its child.
+    ///
+    /// No cycle check is performed: the caller must ensure that `parent` is not in the subtree
+    /// of `id`, or use `move_node`.
+    pub fn set_parent(&mut self, id: &I, parent: Option<I>) -> Result<()> {
+        let is_root = self.root_node.as_ref().is_some_and(|node| node.id() == *id);
+        if !is_root && !self.child_nodes.contains_key(id) {
+            return Err(Error::NodeDoesNotExist);
+        }
+
+        if let Some(parent_id) = parent {
+            if is_root {
+                return Err(Error::RootNodeHasParent);
+            }
+            if self.get_node(&parent_id).is_none() {
+                return Err(Error::ParentNodeDoesNotExist);
+            }
+            self.detach_from_parent(id);
+            if let Some(node) = self.child_nodes.get_mut(id) {
+                node.set_parent_id(parent_id.clone());
+            }
+            if let Some(parent_node) = self.get_mut_node(&parent_id) {
+                parent_node.add_child_id(id.clone());
+            }
+        } else if is_root {
+            if let Some(root_node) = self.root_node.as_mut() {
+                root_node.remove_parent_id();
+            }
         } else {
-            0
+            self.detach_from_parent(id);
+            if let Some(mut node) = self.child_nodes.remove(id) {
+                node.remove_parent_id();
+                if let Some(mut old_root_node) = self.root_node.take() {
+                    old_root_node.set_parent_id(id.clone());
+                    node.add_child_id(old_root_node.id());
+                    self.child_nodes.insert(old_root_node.id(), old_root_node);
+                }
+                self.root_node = Some(node);
+            }
+        }
+        Ok(())
+    }
+
+    /// Move `id` with all its descendants under `new_parent_id`.
+    ///
+    /// Fails with `CycleDetected` if `new_parent_id` is `id` or one of its descendants.
+    pub fn move_node(&mut self, id: &I, new_parent_id: &I) -> Result<()> {
+        let path = self
+            .path_from_root(new_parent_id)
+            .ok_or(Error::ParentNodeDoesNotExist)?;
+        if self.get_node(id).is_none() {
+            return Err(Error::NodeDoesNotExist);
+        }
+        if path.contains(id) {
+            return Err(Error::CycleDetected);
+        }
+        self.set_parent(id, Some(new_parent_id.clone()))
+    }
+
+    /// Move the children of `from`, missing ones included, to the end of the children of `to`.
+    ///
+    /// Fails with `CycleDetected` if `to` is `from` or one of its descendants.
+    pub fn reparent_children(&mut self, from: &I, to: &I) -> Result<()> {
+        let path = self
+            .path_from_root(to)
+            .ok_or(Error::ParentNodeDoesNotExist)?;
+        let child_ids = self
+            .get_node(from)
+            .ok_or(Error::NodeDoesNotExist)?
+            .child_ids_vec();
+        if path.contains(from) {
+            return Err(Error::CycleDetected);
+        }
+        for child_id in child_ids {
+            if self.get_node(&child_id).is_some() {
+                self.set_parent(&child_id, Some(to.clone()))?;
+            } else {
+                if let Some(node) = self.get_mut_node(from) {
+                    node.remove_child_id(&child_id);
+                }
+                if let Some(node) = self.get_mut_node(to) {
+                    node.add_child_id(child_id);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Swap the root for `node` and return the former root.
+    ///
+    /// `node` takes over the child ids of the former root, whose children are re-pointed to the
+    /// id of `node`.
+    pub fn replace_root(&mut self, mut node: N) -> Result<N> {
+        let root_node = self.root_node.as_ref().ok_or(Error::NodeDoesNotExist)?;
+        let (old_id, new_id) = (root_node.id(), node.id());
+        if old_id != new_id && self.child_nodes.contains_key(&new_id) {
+            return Err(Error::NodeAlreadyExists);
+        }
+
+        let child_ids = root_node.child_ids_vec();
+        if old_id != new_id {
+            for child_id in &child_ids {
+                if let Some(child_node) = self.child_nodes.get_mut(child_id) {
+                    child_node.set_parent_id(new_id.clone());
+                }
+            }
+        }
+        node.set_child_ids(child_ids);
+        Ok(self.root_node.replace(node).unwrap())
+    }
+
+    // Remove `id` from the child ids of its parent
+    pub(crate) fn detach_from_parent(&mut self, id: &I) {
+        if let Some(parent_id) = self.get_node(id).and_then(|node| node.parent_id()) {
+            if let Some(parent_node) = self.get_mut_node(&parent_id) {
+                parent_node.remove_child_id(id);
+            }
         }
     }
 
+    /// Existing children of the node grouped by `key`, groups sorted by key.
+    pub fn group_children_by<K: Ord, F: Fn(&N) -> K>(
+        &self,
+        id: &I,
+        key: F,
+    ) -> Option<Vec<(K, Vec<I>)>> {
+        let mut groups: BTreeMap<K, Vec<I>> = BTreeMap::new();
+        for child_id in self.get_node(id)?.child_ids_vec() {
+            if let Some(child) = self.child_nodes.get(&child_id) {
+                groups.entry(key(child)).or_default().push(child_id);
+            }
+        }
+        Some(groups.into_iter().collect())
+    }
+
+    /// Compare the subtree of `id` with the subtree of `other_id` in `other`, by the ids and
+    /// order of their nodes. False if either root is missing or if `id` differs from `other_id`.
+    pub fn subtree_eq(&self, id: &I, other: &RootedTree<I, N>, other_id: &I) -> bool {
+        if id != other_id || self.get_node(id).is_none() || other.get_node(other_id).is_none() {
+            return false;
+        }
+        let mut stack = vec![(self.get_node(id), other.get_node(other_id))];
+        while let Some(nodes) = stack.pop() {
+            match nodes {
+                (Some(node), Some(other_node)) => {
+                    let child_ids = node.child_ids_vec();
+                    if child_ids != other_node.child_ids_vec() {
+                        return false;
+                    }
+                    for child_id in child_ids {
+                        stack.push((
+                            self.child_nodes.get(&child_id),
+                            other.child_nodes.get(&child_id),
+                        ));
+                    }
+                }
+                (None, None) => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// Remove duplicated child ids of every node, keeping the first occurrence.
+    pub fn dedup_children(&mut self) {
+        for node in self
+            .root_node
+            .iter_mut()
+            .chain(self.child_nodes.values_mut())
+        {
+            let mut child_ids = vec![];
+            for child_id in node.child_ids_vec() {
+                if !child_ids.contains(&child_id) {
+                    child_ids.push(child_id);
+                }
+            }
+            node.set_child_ids(child_ids);
+        }
+    }
+
+    /// Apply `f` to each existing direct child of `id`.
+    pub fn for_each_child_mut<F: FnMut(&mut N)>(&mut self, id: &I, mut f: F) -> Result<()> {
+        let child_ids = self
+            .get_node(id)
+            .ok_or(Error::NodeDoesNotExist)?
+            .child_ids_vec();
+        for child_id in child_ids {
+            if let Some(child) = self.child_nodes.get_mut(&child_id) {
+                f(child);
+            }
+        }
+        Ok(())
+    }
+
+    /// Rotate the child ids of `id` left by `n`, modulo the number of children.
+    pub fn rotate_children(&mut self, id: &I, n: usize) -> Result<()> {
+        let node = self.get_mut_node(id).ok_or(Error::NodeDoesNotExist)?;
+        let mut child_ids = node.child_ids_vec();
+        if !child_ids.is_empty() {
+            let len = child_ids.len();
+            child_ids.rotate_left(n % len);
+            node.set_child_ids(child_ids);
+        }
+        Ok(())
+    }
+
+    /// Number of nodes in the subtree of `id`, `id` included, ignoring missing child ids.
+    pub fn subtree_size(&self, id: &I) -> Option<usize> {
+        self.get_node(id)?;
+        let count = self
+            .list_child_ids(id)
+            .iter()
+            .filter(|child_id| self.get_node(child_id).is_some())
+            .count();
+        Some(count + 1)
+    }
+
+    /// Number of distinct ids of the nodes and of their child ids, missing children included.
+    pub fn referenced_id_count(&self) -> usize {
+        let mut ids = HashSet::new();
+        for node in self.root_node.iter().chain(self.child_nodes.values()) {
+            ids.insert(node.id());
+            ids.extend(node.child_ids_vec());
+        }
+        ids.len()
+    }
+
+    /// Child ids listed by a node but without a node in the tree, each once, in pre-order.
+    pub fn dangling_child_ids(&self) -> Vec<I> {
+        let reachable_ids = self.dfs_ids();
+        let reachable_set: HashSet<&I> = reachable_ids.iter().collect();
+        let unreachable_ids = self
+            .child_nodes
+            .keys()
+            .filter(|id| !reachable_set.contains(id));
+
+        let mut dangling_ids = vec![];
+        for id in reachable_ids.iter().chain(unreachable_ids) {
+            for child_id in self
+                .get_node(id)
+                .map(|node| node.child_ids_vec())
+                .unwrap_or_default()
+            {
+                if !self.contains(&child_id) && !dangling_ids.contains(&child_id) {
+                    dangling_ids.push(child_id);
+                }
+            }
+        }
+        dangling_ids
+    }
+
+    pub fn len(&self) -> usize {
+        self.child_nodes.len() + self.root_node.is_some() as usize
+    }
+
+    /// Whether the tree has no root node, child nodes left without a root are ignored.
+    pub fn is_empty(&self) -> bool {
+        self.root_node.is_none()
+    }
+
     pub fn is_subtree(&self) -> bool {
         if let Some(root_node) = &self.root_node {
             root_node.parent_id().is_some()
@@ -129,27 +452,33 @@ impl<I: Eq + PartialEq + Clone + Hash, N: Node<I>> RootedTree<I, N> {
         }
 
         // Return all child ids from root node
-        let root_node = if let Some(node) = self.root_node.as_ref() {
-            if &node.id() == id {
-                if let Some(_) = lvl {
-                    Some(node)
-                } else {
-                    return self.child_nodes.keys().cloned().collect();
-                }
-            } else {
-                None
+        if let Some(node) = self.root_node.as_ref() {
+            if &node.id() == id && lvl.is_none() {
+                return self.child_nodes.keys().cloned().collect();
             }
-        } else {
-            None
-        };
+        }
 
-        // Return all child ids from child node
+        // Explicit stack of (child_id, levels left below it) so that deep trees do not overflow,
+        // `visited` keeps a cyclic `child_ids` graph from looping forever
         let mut out = vec![];
-        if let Some(node) = root_node.or_else(|| self.child_nodes.get(id)) {
-            for child_id in node.child_ids_vec() {
-                out.push(child_id.clone());
-                out.extend(self.list_child_ids_with_lvl(&child_id, lvl.map(|lvl| lvl - 1)));
+        let mut visited = HashSet::from([id.clone()]);
+        let push_children = |stack: &mut Vec<(I, Option<u32>)>, node: &N, lvl: Option<u32>| {
+            for child_id in node.child_ids_vec().into_iter().rev() {
+                stack.push((child_id, lvl.map(|lvl| lvl - 1)));
+            }
+        };
+        let mut stack = vec![];
+        if let Some(node) = self.get_node(id) {
+            push_children(&mut stack, node, lvl);
+        }
+        while let Some((child_id, lvl)) = stack.pop() {
+            if !visited.insert(child_id.clone()) {
+                continue;
             }
+            if let (Some(node), false) = (self.get_node(&child_id), lvl == Some(0)) {
+                push_children(&mut stack, node, lvl);
+            }
+            out.push(child_id);
         }
         out
     }
@@ -187,6 +516,108 @@ impl<I: Eq + PartialEq + Clone + Hash, N: Node<I>> RootedTree<I, N> {
     }
 }
 
+impl<I: Eq + PartialEq + Clone + Hash + Ord, N: Node<I>> RootedTree<I, N> {
+    /// Child ids of the node sorted ascending, the stored order is left untouched.
+    pub fn children_ids_sorted(&self, id: &I) -> Option<Vec<I>> {
+        let mut child_ids = self.get_node(id)?.child_ids_vec();
+        child_ids.sort();
+        Some(child_ids)
+    }
+}
+
+impl<I: Eq + PartialEq + Clone + Hash + Debug, N: Node<I>> RootedTree<I, N> {
+    /// In debug builds, panic if following the parent ids from any node loops back, naming the
+    /// ids of the cycle.
+    pub fn debug_assert_acyclic(&self) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        for node in self.root_node.iter().chain(self.child_nodes.values()) {
+            let mut path = vec![node.id()];
+            let mut parent_id = node.parent_id();
+            while let Some(id) = parent_id {
+                if let Some(index) = path.iter().position(|path_id| path_id == &id) {
+                    let mut cycle = path[index..].to_vec();
+                    cycle.push(id);
+                    panic!("Cycle detected: {:?}", cycle);
+                }
+                parent_id = self.get_node(&id).and_then(|node| node.parent_id());
+                path.push(id);
+            }
+        }
+    }
+}
+
+impl<I: Eq + PartialEq + Clone + Hash + Display, N: Node<I>> RootedTree<I, N> {
+    /// Check that the parent ids have no cycle, that every node listed as a child points back
+    /// to its parent and that every node is reachable from the root.
+    pub fn validate(&self) -> Result<()> {
+        let nodes = || self.root_node.iter().chain(self.child_nodes.values());
+        for node in nodes() {
+            let mut visited = HashSet::from([node.id()]);
+            let mut parent_id = node.parent_id();
+            while let Some(id) = parent_id {
+                if !visited.insert(id.clone()) {
+                    return Err(Error::CycleDetected);
+                }
+                parent_id = self.get_node(&id).and_then(|node| node.parent_id());
+            }
+        }
+
+        for node in nodes() {
+            for child_id in node.child_ids_vec() {
+                if let Some(child) = self.get_node(&child_id) {
+                    if child.parent_id() != Some(node.id()) {
+                        return Err(Error::InconsistentParentLink(child_id.to_string()));
+                    }
+                }
+            }
+        }
+
+        let reachable_ids: HashSet<I> = self.dfs_ids().into_iter().collect();
+        for id in self.child_nodes.keys() {
+            if !reachable_ids.contains(id) {
+                return Err(Error::UnreachableNode(id.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Canonical form of the subtree of `id` as `id(child,child)`, children sorted, so that
+    /// subtrees with the same ids and shape give the same signature whatever their child order.
+    ///
+    /// The characters `\`, `(`, `)`, `,` and `?` of the ids are escaped with a `\`, and the
+    /// ids of missing children are prefixed with `?`.
+    pub fn subtree_signature(&self, id: &I) -> Option<String> {
+        let node = self.get_node(id)?;
+        let mut child_signatures: Vec<String> = node
+            .child_ids_vec()
+            .iter()
+            .map(|child_id| {
+                self.subtree_signature(child_id)
+                    .unwrap_or_else(|| format!("?{}", escape_signature_id(child_id)))
+            })
+            .collect();
+        let id = escape_signature_id(id);
+        if child_signatures.is_empty() {
+            return Some(id);
+        }
+        child_signatures.sort();
+        Some(format!("{}({})", id, child_signatures.join(",")))
+    }
+}
+
+fn escape_signature_id<I: Display>(id: &I) -> String {
+    let mut escaped = String::new();
+    for c in id.to_string().chars() {
+        if matches!(c, '\\' | '(' | ')' | ',' | '?') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
 impl<I: PartialEq + Eq + Hash, N: Node<I> + PartialEq + Eq> PartialEq for RootedTree<I, N> {
     fn eq(&self, other: &Self) -> bool {
         self.root_node == other.root_node && self.child_nodes == other.child_nodes
@@ -221,7 +652,7 @@ mod tests {
         assert_eq!(ids, vec![2, 1, 0]);
 
         let ids = tree.list_parent_ids_with_lvl(&3, Some(0));
-        assert_eq!(ids, vec![]);
+        assert_eq!(ids, Vec::<i32>::new());
     }
 
     #[test]
@@ -251,7 +682,7 @@ mod tests {
         tree.add_node(Some(2), DataNode::new(3)).unwrap();
 
         let ids = tree.list_parent_ids_with_lvl(&1, Some(1));
-        assert_eq!(ids, vec![]);
+        assert_eq!(ids, Vec::<i32>::new());
 
         // From subtree
         tree.get_mut_node(&1).unwrap().set_parent_id(0);
@@ -260,7 +691,7 @@ mod tests {
         assert_eq!(ids, vec![0]);
 
         let ids = tree.list_parent_ids_with_lvl(&1, Some(0));
-        assert_eq!(ids, vec![]);
+        assert_eq!(ids, Vec::<i32>::new());
     }
 
     #[test]
@@ -303,7 +734,7 @@ mod tests {
         tree.add_node(Some(2), DataNode::new(3)).unwrap();
 
         let ids = tree.list_parent_ids(&1);
-        assert_eq!(ids, vec![]);
+        assert_eq!(ids, Vec::<i32>::new());
 
         // From subtree
         tree.get_mut_node(&1).unwrap().set_parent_id(0);
@@ -343,7 +774,7 @@ mod tests {
         tree.add_node(Some(2), DataNode::new(3)).unwrap();
 
         let ids = tree.list_child_ids(&3);
-        assert_eq!(ids, vec![]);
+        assert_eq!(ids, Vec::<i32>::new());
     }
 
     #[test]
@@ -379,7 +810,42 @@ mod tests {
 
         let mut ids = tree.list_child_ids_with_lvl(&3, Some(1));
         ids.sort();
-        assert_eq!(ids, vec![]);
+        assert_eq!(ids, Vec::<i32>::new());
+    }
+
+    #[test]
+    fn list_child_ids_with_lvl_cycle() {
+        let mut tree = RootedTree::<i32, DataNode>::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        // 3 points back to its parent
+        let mut node = DataNode::new(3);
+        node.set_parent_id(2);
+        node.add_child_id(2);
+        tree.set_child_node(node).unwrap();
+
+        assert_eq!(tree.list_child_ids(&2), vec![3]);
+        assert_eq!(tree.list_child_ids(&3), vec![2]);
+        assert_eq!(tree.list_child_ids_with_lvl(&1, Some(10)), vec![2, 3]);
+    }
+
+    #[test]
+    fn deep_chain() {
+        let mut tree = RootedTree::<i32, DataNode>::new();
+        tree.add_node(None, DataNode::new(0)).unwrap();
+        for id in 1..10_000 {
+            tree.add_node(Some(id - 1), DataNode::new(id)).unwrap();
+        }
+
+        assert_eq!(tree.dfs_ids(), (0..10_000).collect::<Vec<_>>());
+        assert_eq!(
+            tree.list_child_ids_with_lvl(&0, Some(20_000)),
+            (1..10_000).collect::<Vec<_>>()
+        );
+        assert_eq!(tree.list_child_ids(&1).len(), 9_998);
+        assert_eq!(tree.subtree_size(&1), Some(9_999));
     }
 
     #[test]
@@ -392,7 +858,7 @@ mod tests {
         let node = r_tree.get_node(&1).unwrap();
         assert_eq!(node.id(), 1);
         assert_eq!(node.parent_id(), None);
-        assert_eq!(node.child_ids_vec(), vec![]);
+        assert_eq!(node.child_ids_vec(), Vec::<i32>::new());
     }
 
     #[test]
@@ -411,7 +877,7 @@ mod tests {
         let node_2 = r_tree.get_node(&2).unwrap();
         assert_eq!(node_2.id(), 2);
         assert_eq!(node_2.parent_id(), Some(1));
-        assert_eq!(node_2.child_ids_vec(), vec![]);
+        assert_eq!(node_2.child_ids_vec(), Vec::<i32>::new());
     }
 
     #[test]
@@ -447,6 +913,347 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parent_id() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        assert_eq!(r_tree.parent_id(&2), Some(1));
+        assert_eq!(r_tree.parent_id(&1), None);
+        assert_eq!(r_tree.parent_id(&3), None);
+
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        let mut node = DataNode::new(1);
+        node.set_parent_id(0);
+        r_tree.set_root_node(node);
+        assert_eq!(r_tree.parent_id(&1), Some(0));
+    }
+
+    #[test]
+    fn move_node() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        r_tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(4)).unwrap();
+
+        r_tree.move_node(&2, &4).unwrap();
+
+        assert_eq!(r_tree.get_node(&1).unwrap().child_ids_vec(), vec![4]);
+        assert_eq!(r_tree.get_node(&4).unwrap().child_ids_vec(), vec![2]);
+        assert_eq!(r_tree.parent_id(&2), Some(4));
+        assert_eq!(r_tree.parent_id(&3), Some(2));
+        assert_eq!(r_tree.list_child_ids(&4), vec![2, 3]);
+    }
+
+    #[test]
+    fn root() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        assert!(r_tree.root().is_none());
+        assert_eq!(r_tree.root_id(), None);
+
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        assert_eq!(r_tree.root_id(), Some(1));
+        assert_eq!(r_tree.root().unwrap().child_ids_vec(), vec![2]);
+    }
+
+    #[test]
+    fn contains() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        assert!(!r_tree.contains(&1));
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        let mut node = DataNode::new(2);
+        node.add_child_id(3);
+        r_tree.add_node(Some(1), node).unwrap();
+
+        assert!(r_tree.contains(&1));
+        assert!(r_tree.contains(&2));
+        assert!(!r_tree.contains(&3));
+        assert!(!r_tree.contains(&4));
+    }
+
+    #[test]
+    fn reparent_children() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        r_tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        let mut node = DataNode::new(4);
+        node.add_child_id(7);
+        r_tree.add_node(Some(2), node).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(5)).unwrap();
+        r_tree.add_node(Some(5), DataNode::new(6)).unwrap();
+        r_tree.get_mut_node(&2).unwrap().add_child_id(8);
+
+        r_tree.reparent_children(&2, &5).unwrap();
+
+        assert_eq!(
+            r_tree.get_node(&2).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
+        assert_eq!(
+            r_tree.get_node(&5).unwrap().child_ids_vec(),
+            vec![6, 3, 4, 8]
+        );
+        assert_eq!(r_tree.parent_id(&3), Some(5));
+        assert_eq!(r_tree.parent_id(&4), Some(5));
+        assert_eq!(r_tree.list_child_ids(&4), vec![7]);
+        assert!(r_tree.leaves().contains(&2));
+    }
+
+    #[test]
+    fn reparent_children_errors() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        r_tree.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        assert!(matches!(
+            r_tree.reparent_children(&1, &3),
+            Err(Error::CycleDetected)
+        ));
+        assert!(matches!(
+            r_tree.reparent_children(&2, &2),
+            Err(Error::CycleDetected)
+        ));
+        assert!(matches!(
+            r_tree.reparent_children(&2, &10),
+            Err(Error::ParentNodeDoesNotExist)
+        ));
+        assert!(matches!(
+            r_tree.reparent_children(&10, &1),
+            Err(Error::NodeDoesNotExist)
+        ));
+        assert_eq!(r_tree.list_child_ids(&2), vec![3]);
+    }
+
+    #[test]
+    fn replace_root_same_id() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        let mut node = DataNode::new(1);
+        node.value = 10;
+        r_tree.add_node(None, node).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+
+        let mut node = DataNode::new(1);
+        node.value = 20;
+        let old_root = r_tree.replace_root(node).unwrap();
+
+        assert_eq!(old_root.value, 10);
+        assert_eq!(r_tree.get_node(&1).unwrap().value, 20);
+        assert_eq!(r_tree.get_node(&1).unwrap().child_ids_vec(), vec![2]);
+        assert_eq!(r_tree.parent_id(&2), Some(1));
+    }
+
+    #[test]
+    fn replace_root_new_id() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        r_tree.add_node(Some(2), DataNode::new(4)).unwrap();
+
+        let old_root = r_tree.replace_root(DataNode::new(10)).unwrap();
+
+        assert_eq!(old_root.id(), 1);
+        assert!(r_tree.get_node(&1).is_none());
+        assert_eq!(r_tree.len(), 4);
+        assert_eq!(r_tree.get_node(&10).unwrap().child_ids_vec(), vec![2, 3]);
+        assert_eq!(r_tree.parent_id(&2), Some(10));
+        assert_eq!(r_tree.parent_id(&3), Some(10));
+        assert_eq!(r_tree.parent_id(&4), Some(2));
+        assert_eq!(r_tree.path_from_root(&4), Some(vec![10, 2, 4]));
+
+        assert!(matches!(
+            r_tree.replace_root(DataNode::new(4)),
+            Err(Error::NodeAlreadyExists)
+        ));
+        assert!(matches!(
+            RootedTree::<i32, DataNode>::new().replace_root(DataNode::new(1)),
+            Err(Error::NodeDoesNotExist)
+        ));
+    }
+
+    #[test]
+    fn move_node_leaf() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        r_tree.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        r_tree.move_node(&3, &1).unwrap();
+
+        assert_eq!(r_tree.get_node(&1).unwrap().child_ids_vec(), vec![2, 3]);
+        assert_eq!(
+            r_tree.get_node(&2).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
+        assert_eq!(r_tree.parent_id(&3), Some(1));
+    }
+
+    #[test]
+    fn move_node_errors() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        r_tree.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        assert!(matches!(
+            r_tree.move_node(&2, &3),
+            Err(Error::CycleDetected)
+        ));
+        assert!(matches!(
+            r_tree.move_node(&2, &2),
+            Err(Error::CycleDetected)
+        ));
+        assert!(matches!(
+            r_tree.move_node(&2, &10),
+            Err(Error::ParentNodeDoesNotExist)
+        ));
+        assert!(matches!(
+            r_tree.move_node(&10, &1),
+            Err(Error::NodeDoesNotExist)
+        ));
+        assert_eq!(r_tree.parent_id(&2), Some(1));
+        assert_eq!(
+            r_tree.get_node(&3).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
+    }
+
+    #[test]
+    fn sibling_index() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        for id in [2, 3, 4] {
+            r_tree.add_node(Some(1), DataNode::new(id)).unwrap();
+        }
+        assert_eq!(r_tree.sibling_index(&2), Some(0));
+        assert_eq!(r_tree.sibling_index(&3), Some(1));
+        assert_eq!(r_tree.sibling_index(&4), Some(2));
+        assert_eq!(r_tree.sibling_index(&1), None);
+        assert_eq!(r_tree.sibling_index(&5), None);
+    }
+
+    #[test]
+    fn siblings() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        for id in [2, 3, 4] {
+            r_tree.add_node(Some(1), DataNode::new(id)).unwrap();
+        }
+        r_tree.add_node(Some(2), DataNode::new(5)).unwrap();
+
+        assert_eq!(r_tree.siblings(&3), vec![2, 4]);
+        assert_eq!(r_tree.siblings(&5), Vec::<i32>::new());
+        assert_eq!(r_tree.siblings(&1), Vec::<i32>::new());
+        assert_eq!(r_tree.siblings(&6), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn common_children() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        let mut node = DataNode::new(2);
+        for child_id in [5, 6, 7] {
+            node.add_child_id(child_id);
+        }
+        r_tree.add_node(Some(1), node).unwrap();
+        let mut node = DataNode::new(3);
+        for child_id in [7, 8, 5] {
+            node.add_child_id(child_id);
+        }
+        r_tree.add_node(Some(1), node).unwrap();
+
+        assert_eq!(r_tree.common_children(&2, &3), vec![5, 7]);
+        assert_eq!(r_tree.common_children(&3, &2), vec![7, 5]);
+        assert_eq!(r_tree.common_children(&1, &2), Vec::<i32>::new());
+        assert_eq!(r_tree.common_children(&2, &4), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn subtree_size() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        r_tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        let mut node = DataNode::new(4);
+        node.add_child_id(5);
+        r_tree.add_node(Some(2), node).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(6)).unwrap();
+
+        assert_eq!(r_tree.subtree_size(&1), Some(r_tree.len()));
+        assert_eq!(r_tree.subtree_size(&2), Some(3));
+        assert_eq!(r_tree.subtree_size(&4), Some(1));
+        assert_eq!(r_tree.subtree_size(&6), Some(1));
+        assert_eq!(r_tree.subtree_size(&5), None);
+    }
+
+    #[test]
+    fn referenced_id_count() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        assert_eq!(r_tree.referenced_id_count(), 0);
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        assert_eq!(r_tree.referenced_id_count(), r_tree.len());
+
+        let mut node = DataNode::new(3);
+        node.add_child_id(4);
+        node.add_child_id(5);
+        r_tree.add_node(Some(1), node).unwrap();
+        let mut node = DataNode::new(6);
+        node.add_child_id(7);
+        r_tree.add_node(Some(2), node).unwrap();
+
+        let missing_child_ids: Vec<i32> = r_tree
+            .child_nodes
+            .values()
+            .flat_map(|node| node.child_ids_vec())
+            .filter(|child_id| r_tree.get_node(child_id).is_none())
+            .collect();
+        assert_eq!(missing_child_ids.len(), 3);
+        assert_eq!(
+            r_tree.referenced_id_count() - r_tree.len(),
+            missing_child_ids.len()
+        );
+    }
+
+    #[test]
+    fn dangling_child_ids() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        assert_eq!(r_tree.dangling_child_ids(), Vec::<i32>::new());
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        let mut node = DataNode::new(2);
+        node.set_parent_id(1);
+        node.add_child_id(3);
+        node.add_child_id(4);
+        node.add_child_id(5);
+        r_tree.add_node(Some(1), node).unwrap();
+        r_tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        assert_eq!(r_tree.dangling_child_ids(), vec![3, 5]);
+
+        r_tree.get_mut_node(&4).unwrap().add_child_id(3);
+        r_tree.get_mut_node(&1).unwrap().add_child_id(6);
+        assert_eq!(r_tree.dangling_child_ids(), vec![6, 3, 5]);
+        assert_eq!(
+            r_tree.referenced_id_count() - r_tree.len(),
+            r_tree.dangling_child_ids().len()
+        );
+    }
+
+    #[test]
+    fn is_empty() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        assert!(r_tree.is_empty());
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        assert!(!r_tree.is_empty());
+
+        r_tree.child_nodes.insert(2, DataNode::new(2));
+        r_tree.root_node = None;
+        assert!(r_tree.is_empty());
+    }
+
     #[test]
     fn remove_root_node() {
         let mut r_tree = RootedTree::<i32, DataNode>::new();
@@ -457,6 +1264,40 @@ mod tests {
         assert_eq!(r_tree.len(), 0);
     }
 
+    #[test]
+    fn remove_root_node_with_children() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        r_tree.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        assert_eq!(r_tree.remove_node(&1).unwrap().id(), 1);
+        assert_eq!(r_tree.len(), 0);
+        assert!(r_tree.get_node(&2).is_none());
+        assert!(r_tree.remove_node(&2).is_none());
+    }
+
+    #[test]
+    fn remove_root_node_by_id() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        let node = r_tree.remove_node(&1).unwrap();
+        assert_eq!(node.id(), 1);
+        assert!(r_tree.get_node(&1).is_none());
+    }
+
+    #[test]
+    fn remove_unknown_node() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        assert!(r_tree.remove_node(&1).is_none());
+
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        assert!(r_tree.remove_node(&99999).is_none());
+        assert_eq!(r_tree.len(), 2);
+        assert_eq!(r_tree.get_node(&1).unwrap().child_ids_vec(), vec![2]);
+    }
+
     #[test]
     fn remove_child_node() {
         let mut r_tree = RootedTree::<i32, DataNode>::new();
@@ -470,7 +1311,7 @@ mod tests {
         let node = r_tree.get_node(&1).unwrap();
         assert_eq!(node.id(), 1);
         assert_eq!(node.parent_id(), None);
-        assert_eq!(node.child_ids_vec(), vec![]);
+        assert_eq!(node.child_ids_vec(), Vec::<i32>::new());
     }
 
     #[test]
@@ -495,7 +1336,7 @@ mod tests {
         let node = r_tree.get_node(&1).unwrap();
         assert_eq!(node.id(), 1);
         assert_eq!(node.parent_id(), None);
-        assert_eq!(node.child_ids_vec(), vec![]);
+        assert_eq!(node.child_ids_vec(), Vec::<i32>::new());
     }
 
     #[test]
@@ -515,7 +1356,7 @@ mod tests {
         let node = r_tree.get_node(&2).unwrap();
         assert_eq!(node.id(), 2);
         assert_eq!(node.parent_id(), Some(1));
-        assert_eq!(node.child_ids_vec(), vec![]);
+        assert_eq!(node.child_ids_vec(), Vec::<i32>::new());
     }
 
     #[test]
@@ -574,5 +1415,314 @@ mod tests {
         assert!(tree_2 != tree_1);
     }
 
+    #[test]
+    fn children_ids_sorted() {
+        let mut tree = RootedTree::<i32, DataNode>::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(4)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+
+        assert_eq!(tree.children_ids_sorted(&1), Some(vec![2, 3, 4]));
+        assert_eq!(tree.get_node(&1).unwrap().child_ids_vec(), vec![4, 2, 3]);
+        assert_eq!(tree.children_ids_sorted(&2), Some(vec![]));
+        assert_eq!(tree.children_ids_sorted(&5), None);
+    }
+
+    #[test]
+    fn set_parent() {
+        let mut tree = RootedTree::<i32, DataNode>::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+
+        tree.set_parent(&4, Some(3)).unwrap();
+
+        assert_eq!(
+            tree.get_node(&2).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
+        assert_eq!(tree.get_node(&3).unwrap().child_ids_vec(), vec![4]);
+        assert_eq!(tree.get_node(&4).unwrap().parent_id(), Some(3));
+    }
+
+    #[test]
+    fn set_parent_root_swap() {
+        let mut tree = RootedTree::<i32, DataNode>::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+
+        tree.set_parent(&2, None).unwrap();
+
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.root_node.as_ref().unwrap().id(), 2);
+        assert_eq!(tree.get_node(&2).unwrap().parent_id(), None);
+        assert_eq!(tree.get_node(&2).unwrap().child_ids_vec(), vec![1]);
+        assert_eq!(tree.get_node(&1).unwrap().parent_id(), Some(2));
+        assert_eq!(tree.get_node(&1).unwrap().child_ids_vec(), vec![3]);
+        assert!(!tree.is_subtree());
+        assert!(tree.validate().is_ok());
+    }
+
+    #[test]
+    fn fail_to_set_parent() {
+        let mut tree = RootedTree::<i32, DataNode>::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+
+        assert!(matches!(
+            tree.set_parent(&3, Some(1)),
+            Err(Error::NodeDoesNotExist)
+        ));
+        assert!(matches!(
+            tree.set_parent(&2, Some(3)),
+            Err(Error::ParentNodeDoesNotExist)
+        ));
+        assert!(matches!(
+            tree.set_parent(&1, Some(2)),
+            Err(Error::RootNodeHasParent)
+        ));
+    }
+
+    #[test]
+    fn dedup_children() {
+        let mut tree = RootedTree::<i32, DataNode>::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.get_mut_node(&1).unwrap().child_ids = vec![3, 2, 3, 2, 4];
+
+        tree.dedup_children();
+
+        assert_eq!(tree.get_node(&1).unwrap().child_ids_vec(), vec![3, 2, 4]);
+        assert_eq!(
+            tree.get_node(&2).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
+    }
+
+    #[test]
+    fn group_children_by() {
+        let mut tree = RootedTree::<i32, DataNode>::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        for id in [5, 2, 3, 4, 6] {
+            tree.add_node(Some(1), DataNode::new(id)).unwrap();
+        }
+
+        let groups = tree.group_children_by(&1, |node| node.id() % 2).unwrap();
+
+        assert_eq!(groups, vec![(0, vec![2, 4, 6]), (1, vec![5, 3])]);
+        assert_eq!(
+            tree.group_children_by(&2, |node| node.id() % 2),
+            Some(vec![])
+        );
+        assert_eq!(tree.group_children_by(&7, |node| node.id() % 2), None);
+    }
+
+    #[test]
+    fn subtree_eq() {
+        let mut tree_1 = RootedTree::<i32, DataNode>::new();
+        tree_1.add_node(None, DataNode::new(1)).unwrap();
+        tree_1.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree_1.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree_1.add_node(Some(2), DataNode::new(4)).unwrap();
+
+        let mut tree_2 = RootedTree::<i32, DataNode>::new();
+        tree_2.add_node(None, DataNode::new(10)).unwrap();
+        tree_2.add_node(Some(10), DataNode::new(2)).unwrap();
+        tree_2.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree_2.add_node(Some(2), DataNode::new(4)).unwrap();
+
+        assert!(tree_1.subtree_eq(&2, &tree_2, &2));
+        assert!(!tree_1.subtree_eq(&1, &tree_2, &10));
+
+        tree_2.add_node(Some(4), DataNode::new(5)).unwrap();
+
+        assert!(!tree_1.subtree_eq(&2, &tree_2, &2));
+        assert!(tree_1.subtree_eq(&3, &tree_2, &3));
+        assert!(!tree_1.subtree_eq(&3, &tree_2, &6));
+        assert!(!tree_1.subtree_eq(&6, &tree_2, &6));
+        assert!(!tree_1.subtree_eq(&3, &tree_2, &4));
+    }
+
+    #[test]
+    fn for_each_child_mut() {
+        let mut tree = RootedTree::<i32, DataNode>::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        let mut node = DataNode::new(2);
+        node.add_child_id(5);
+        tree.add_node(Some(1), node).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+
+        tree.for_each_child_mut(&1, |node| node.value += 10)
+            .unwrap();
+        tree.for_each_child_mut(&2, |node| node.value += 1).unwrap();
+
+        assert_eq!(tree.get_node(&1).unwrap().value, 0);
+        assert_eq!(tree.get_node(&2).unwrap().value, 10);
+        assert_eq!(tree.get_node(&3).unwrap().value, 10);
+        assert_eq!(tree.get_node(&4).unwrap().value, 1);
+        assert!(matches!(
+            tree.for_each_child_mut(&6, |node| node.value += 1),
+            Err(Error::NodeDoesNotExist)
+        ));
+    }
+
+    #[test]
+    fn rotate_children() {
+        let mut tree = RootedTree::<i32, DataNode>::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        for id in [2, 3, 4, 5] {
+            tree.add_node(Some(1), DataNode::new(id)).unwrap();
+        }
+
+        tree.rotate_children(&1, 1).unwrap();
+        assert_eq!(tree.get_node(&1).unwrap().child_ids_vec(), vec![3, 4, 5, 2]);
+
+        tree.rotate_children(&1, 6).unwrap();
+        assert_eq!(tree.get_node(&1).unwrap().child_ids_vec(), vec![5, 2, 3, 4]);
+
+        tree.rotate_children(&2, 3).unwrap();
+        assert_eq!(
+            tree.get_node(&2).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
+
+        assert!(matches!(
+            tree.rotate_children(&10, 1),
+            Err(Error::NodeDoesNotExist)
+        ));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn debug_assert_acyclic() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        r_tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        r_tree.debug_assert_acyclic();
+
+        r_tree.get_mut_node(&2).unwrap().parent_id = Some(3);
+        r_tree.get_mut_node(&3).unwrap().child_ids = vec![2];
+        let result = std::panic::catch_unwind(|| r_tree.debug_assert_acyclic());
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.starts_with("Cycle detected: "));
+        assert!(message.contains('2') && message.contains('3'));
+    }
+
+    #[test]
+    fn subtree_signature() {
+        let mut tree_1 = RootedTree::<i32, DataNode>::new();
+        tree_1.add_node(None, DataNode::new(1)).unwrap();
+        tree_1.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree_1.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree_1.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree_1.add_node(Some(4), DataNode::new(5)).unwrap();
+
+        let mut tree_2 = RootedTree::<i32, DataNode>::new();
+        tree_2.add_node(None, DataNode::new(10)).unwrap();
+        tree_2.add_node(Some(10), DataNode::new(2)).unwrap();
+        tree_2.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree_2.add_node(Some(4), DataNode::new(5)).unwrap();
+        tree_2.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        assert_eq!(tree_1.subtree_signature(&2), Some("2(3,4(5))".to_string()));
+        assert_eq!(tree_1.subtree_signature(&2), tree_2.subtree_signature(&2));
+        assert_ne!(tree_1.subtree_signature(&1), tree_2.subtree_signature(&10));
+        assert_ne!(tree_1.subtree_signature(&2), tree_1.subtree_signature(&4));
+        assert_eq!(tree_1.subtree_signature(&6), None);
+
+        tree_1.get_mut_node(&3).unwrap().add_child_id(6);
+        assert_eq!(tree_1.subtree_signature(&3), Some("3(?6)".to_string()));
+        tree_1.add_node(Some(3), DataNode::new(6)).unwrap();
+        assert_eq!(tree_1.subtree_signature(&3), Some("3(6)".to_string()));
+    }
+
+    #[test]
+    fn subtree_signature_escaped_ids() {
+        let mut tree_1 = RootedTree::<String, PathNode>::new();
+        tree_1.add_node(None, PathNode::new("a")).unwrap();
+        tree_1
+            .add_node(Some("a".to_string()), PathNode::new("b,c"))
+            .unwrap();
+
+        let mut tree_2 = RootedTree::<String, PathNode>::new();
+        tree_2.add_node(None, PathNode::new("a")).unwrap();
+        tree_2
+            .add_node(Some("a".to_string()), PathNode::new("b"))
+            .unwrap();
+        tree_2
+            .add_node(Some("a".to_string()), PathNode::new("c"))
+            .unwrap();
+
+        assert_eq!(
+            tree_1.subtree_signature(&"a".to_string()),
+            Some("a(b\\,c)".to_string())
+        );
+        assert_eq!(
+            tree_2.subtree_signature(&"a".to_string()),
+            Some("a(b,c)".to_string())
+        );
+    }
+
+    #[test]
+    fn validate() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.validate().unwrap();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        r_tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        let mut node = DataNode::new(4);
+        node.add_child_id(5);
+        r_tree.add_node(Some(1), node).unwrap();
+        r_tree.validate().unwrap();
+        r_tree.clone_from(2).unwrap().validate().unwrap();
+    }
+
+    #[test]
+    fn validate_cycle() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        r_tree.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        r_tree.get_mut_node(&2).unwrap().parent_id = Some(3);
+        r_tree.get_mut_node(&3).unwrap().child_ids = vec![2];
+        assert!(matches!(r_tree.validate(), Err(Error::CycleDetected)));
+    }
+
+    #[test]
+    fn validate_unreachable_node() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+
+        let mut node = DataNode::new(9);
+        node.set_parent_id(1);
+        r_tree.child_nodes.insert(9, node);
+        assert!(matches!(
+            r_tree.validate(),
+            Err(Error::UnreachableNode(id)) if id == "9"
+        ));
+    }
+
+    #[test]
+    fn validate_inconsistent_parent_link() {
+        let mut r_tree = RootedTree::<i32, DataNode>::new();
+        r_tree.add_node(None, DataNode::new(1)).unwrap();
+        r_tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        r_tree.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        r_tree.get_mut_node(&3).unwrap().parent_id = Some(1);
+        assert!(matches!(
+            r_tree.validate(),
+            Err(Error::InconsistentParentLink(id)) if id == "3"
+        ));
+    }
+
     // TODO: Test add node with the same parent id
 }
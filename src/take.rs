@@ -1,4 +1,5 @@
-use crate::{Node, RootedTree};
+use crate::{Error, Node, Result, RootedTree};
+use std::collections::HashSet;
 use std::hash::Hash;
 
 impl<I: Eq + PartialEq + Clone + Hash, N: Node<I>> RootedTree<I, N> {
@@ -27,6 +28,122 @@ impl<I: Eq + PartialEq + Clone + Hash, N: Node<I>> RootedTree<I, N> {
 
         Some(sub_tree)
     }
+
+    /// Remove `id` with all its descendants and return the removed nodes, `id` first.
+    ///
+    /// Removing the root empties the tree.
+    pub fn remove_subtree(&mut self, id: &I) -> Vec<N> {
+        if self.get_node(id).is_none() {
+            return vec![];
+        }
+        // Descendants first, removing the root clears the child nodes
+        let child_nodes: Vec<N> = self
+            .list_child_ids(id)
+            .iter()
+            .filter_map(|child_id| self.child_nodes.remove(child_id))
+            .collect();
+        let mut nodes: Vec<N> = self.remove_node(id).into_iter().collect();
+        nodes.extend(child_nodes);
+        nodes
+    }
+
+    /// Cut the tree below `lvl` and return the removed subtrees, rooted at depth `lvl + 1`.
+    pub fn prune_to_depth_returning(&mut self, lvl: u32) -> Vec<RootedTree<I, N>> {
+        let Some(cut_lvl) = lvl.checked_add(1) else {
+            return vec![];
+        };
+        let mut sub_trees = vec![];
+        for id in self.ids_at_depth(cut_lvl) {
+            if let Some(sub_tree) = self.take(id.clone()) {
+                if let Some(parent_id) = sub_tree
+                    .root_node
+                    .as_ref()
+                    .and_then(|node| node.parent_id())
+                {
+                    if let Some(parent_node) = self.get_mut_node(&parent_id) {
+                        parent_node.remove_child_id(&id);
+                    }
+                }
+                sub_trees.push(sub_tree);
+            }
+        }
+        sub_trees
+    }
+
+    /// Keep only the nodes of `ids` and their ancestors, dropping everything else.
+    pub fn keep_ids_with_ancestors(&mut self, ids: &[I]) -> Result<()> {
+        let mut keep_ids = HashSet::new();
+        for id in ids {
+            keep_ids.extend(self.path_from_root(id).ok_or(Error::NodeDoesNotExist)?);
+        }
+
+        if !self
+            .root_node
+            .as_ref()
+            .is_some_and(|node| keep_ids.contains(&node.id()))
+        {
+            self.root_node = None;
+        }
+        self.child_nodes.retain(|id, _| keep_ids.contains(id));
+        for node in self
+            .root_node
+            .iter_mut()
+            .chain(self.child_nodes.values_mut())
+        {
+            let child_ids = node
+                .child_ids_vec()
+                .into_iter()
+                .filter(|child_id| keep_ids.contains(child_id))
+                .collect();
+            node.set_child_ids(child_ids);
+        }
+        Ok(())
+    }
+
+    /// Keep only the nodes of `keep` still linked to the root, dropping the others with their
+    /// descendants. The tree is emptied if the root is not kept.
+    pub fn retain_ids(&mut self, keep: &HashSet<I>) {
+        let root_id = match &self.root_node {
+            Some(root_node) if keep.contains(&root_node.id()) => root_node.id(),
+            _ => {
+                self.root_node = None;
+                self.child_nodes.clear();
+                return;
+            }
+        };
+
+        let mut linked_ids = HashSet::new();
+        let mut stack = vec![root_id];
+        while let Some(id) = stack.pop() {
+            if !linked_ids.insert(id.clone()) {
+                continue;
+            }
+            if let Some(node) = self.get_mut_node(&id) {
+                let child_ids: Vec<I> = node
+                    .child_ids_vec()
+                    .into_iter()
+                    .filter(|child_id| keep.contains(child_id))
+                    .collect();
+                node.set_child_ids(child_ids.clone());
+                stack.extend(child_ids);
+            }
+        }
+        self.child_nodes.retain(|id, _| linked_ids.contains(id));
+    }
+
+    /// Keep only the subtree of `id`, which becomes the root of the tree.
+    pub fn keep_only_subtree(&mut self, id: &I) -> Result<()> {
+        if self.get_node(id).is_none() {
+            return Err(Error::NodeDoesNotExist);
+        }
+        if let Some(mut sub_tree) = self.take(id.clone()) {
+            if let Some(root_node) = sub_tree.root_node.as_mut() {
+                root_node.remove_parent_id();
+            }
+            *self = sub_tree;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -66,7 +183,10 @@ mod tests {
         assert_eq!(sub_tree.get_node(&2).unwrap().parent_id(), Some(1));
         assert_eq!(sub_tree.get_node(&2).unwrap().child_ids_vec(), vec![3]);
         assert_eq!(sub_tree.get_node(&3).unwrap().parent_id(), Some(2));
-        assert_eq!(sub_tree.get_node(&3).unwrap().child_ids_vec(), vec![]);
+        assert_eq!(
+            sub_tree.get_node(&3).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
     }
 
     #[test]
@@ -86,6 +206,164 @@ mod tests {
 
         assert_eq!(sub_tree.len(), 1);
         assert_eq!(sub_tree.get_node(&3).unwrap().parent_id(), Some(2));
-        assert_eq!(sub_tree.get_node(&3).unwrap().child_ids_vec(), vec![]);
+        assert_eq!(
+            sub_tree.get_node(&3).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
+    }
+
+    #[test]
+    fn keep_only_subtree() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(4)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        tree.keep_only_subtree(&2).unwrap();
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree.root_node.as_ref().unwrap().id(), 2);
+        assert_eq!(tree.get_node(&2).unwrap().parent_id(), None);
+        assert_eq!(tree.get_node(&2).unwrap().child_ids_vec(), vec![3]);
+        assert!(tree.get_node(&1).is_none());
+        assert!(tree.get_node(&4).is_none());
+    }
+
+    #[test]
+    fn fail_to_keep_only_subtree_node_does_not_exist() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+
+        assert!(matches!(
+            tree.keep_only_subtree(&2),
+            Err(Error::NodeDoesNotExist)
+        ));
+        assert_eq!(tree.len(), 1);
+    }
+
+    #[test]
+    fn prune_to_depth_returning() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(4), DataNode::new(5)).unwrap();
+        tree.add_node(Some(3), DataNode::new(6)).unwrap();
+
+        let sub_trees = tree.prune_to_depth_returning(1);
+
+        assert_eq!(tree.len(), 3);
+        assert_eq!(
+            tree.get_node(&2).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
+        assert_eq!(
+            tree.get_node(&3).unwrap().child_ids_vec(),
+            Vec::<i32>::new()
+        );
+
+        assert_eq!(sub_trees.len(), 2);
+        assert_eq!(sub_trees[0].len(), 2);
+        assert_eq!(sub_trees[0].get_node(&4).unwrap().parent_id(), Some(2));
+        assert_eq!(sub_trees[0].get_node(&4).unwrap().child_ids_vec(), vec![5]);
+        assert_eq!(sub_trees[1].len(), 1);
+        assert_eq!(sub_trees[1].get_node(&6).unwrap().parent_id(), Some(3));
+
+        assert!(tree.prune_to_depth_returning(u32::MAX).is_empty());
+        assert_eq!(tree.len(), 3);
+    }
+
+    #[test]
+    fn remove_subtree() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(3), DataNode::new(4)).unwrap();
+        tree.add_node(Some(1), DataNode::new(5)).unwrap();
+
+        let mut removed_ids: Vec<i32> = tree.remove_subtree(&2).iter().map(|n| n.id()).collect();
+        removed_ids.sort();
+
+        assert_eq!(removed_ids, vec![2, 3, 4]);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree.get_node(&1).unwrap().child_ids_vec(), vec![5]);
+        assert!(tree.get_node(&3).is_none());
+        assert!(tree.get_node(&4).is_none());
+    }
+
+    #[test]
+    fn remove_subtree_root() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        let removed = tree.remove_subtree(&1);
+
+        assert_eq!(removed.len(), 3);
+        assert_eq!(removed[0].id(), 1);
+        assert_eq!(tree.len(), 0);
+        assert!(tree.remove_subtree(&1).is_empty());
+    }
+
+    #[test]
+    fn retain_ids() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(1), DataNode::new(4)).unwrap();
+        tree.add_node(Some(4), DataNode::new(5)).unwrap();
+        tree.add_node(Some(1), DataNode::new(6)).unwrap();
+
+        // 5 is kept but its parent is not
+        tree.retain_ids(&HashSet::from([1, 2, 3, 5, 6]));
+
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.get_node(&1).unwrap().child_ids_vec(), vec![2, 6]);
+        assert_eq!(tree.get_node(&2).unwrap().child_ids_vec(), vec![3]);
+        assert!(tree.get_node(&4).is_none());
+        assert!(tree.get_node(&5).is_none());
+
+        tree.retain_ids(&HashSet::from([2, 3]));
+        assert!(tree.is_empty());
+        assert_eq!(tree.len(), 0);
+    }
+
+    #[test]
+    fn keep_ids_with_ancestors() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(1), DataNode::new(5)).unwrap();
+        tree.add_node(Some(5), DataNode::new(6)).unwrap();
+        tree.add_node(Some(5), DataNode::new(7)).unwrap();
+        tree.add_node(Some(1), DataNode::new(8)).unwrap();
+
+        tree.keep_ids_with_ancestors(&[3, 7]).unwrap();
+
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.get_node(&1).unwrap().child_ids_vec(), vec![2, 5]);
+        assert_eq!(tree.get_node(&2).unwrap().child_ids_vec(), vec![3]);
+        assert_eq!(tree.get_node(&5).unwrap().child_ids_vec(), vec![7]);
+        assert!(tree.get_node(&8).is_none());
+    }
+
+    #[test]
+    fn keep_ids_with_ancestors_missing() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+
+        assert!(matches!(
+            tree.keep_ids_with_ancestors(&[2, 3]),
+            Err(Error::NodeDoesNotExist)
+        ));
+        assert_eq!(tree.len(), 2);
     }
 }
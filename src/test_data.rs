@@ -1,10 +1,12 @@
-use crate::Node;
+use crate::{Node, RootedTree};
 
 #[derive(Eq, PartialEq, Clone)]
 pub struct DataNode {
     pub(crate) id: i32,
     pub(crate) parent_id: Option<i32>,
     pub(crate) child_ids: Vec<i32>,
+    pub(crate) value: i32,
+    pub(crate) edge_label: Option<String>,
 }
 
 impl DataNode {
@@ -13,6 +15,8 @@ impl DataNode {
             id,
             parent_id: None,
             child_ids: vec![],
+            value: 0,
+            edge_label: None,
         }
     }
 }
@@ -34,6 +38,10 @@ impl Node<i32> for DataNode {
         self.parent_id = Some(parent);
     }
 
+    fn remove_parent_id(&mut self) {
+        self.parent_id = None;
+    }
+
     fn add_child_id(&mut self, child_id: i32) {
         if self.child_ids.contains(&child_id) {
             return;
@@ -44,4 +52,76 @@ impl Node<i32> for DataNode {
     fn remove_child_id(&mut self, child_id: &i32) {
         self.child_ids.retain(|id| id != child_id);
     }
+
+    fn edge_label(&self) -> Option<String> {
+        self.edge_label.clone()
+    }
+}
+
+#[derive(Eq, PartialEq, Clone)]
+pub struct PathNode {
+    pub(crate) id: String,
+    pub(crate) parent_id: Option<String>,
+    pub(crate) child_ids: Vec<String>,
+}
+
+impl PathNode {
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            parent_id: None,
+            child_ids: vec![],
+        }
+    }
+}
+
+impl Node<String> for PathNode {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn parent_id(&self) -> Option<String> {
+        self.parent_id.clone()
+    }
+
+    fn child_ids_vec(&self) -> Vec<String> {
+        self.child_ids.clone()
+    }
+
+    fn set_parent_id(&mut self, parent: String) {
+        self.parent_id = Some(parent);
+    }
+
+    fn remove_parent_id(&mut self) {
+        self.parent_id = None;
+    }
+
+    fn add_child_id(&mut self, child_id: String) {
+        if self.child_ids.contains(&child_id) {
+            return;
+        }
+        self.child_ids.push(child_id);
+    }
+
+    fn remove_child_id(&mut self, child_id: &String) {
+        self.child_ids.retain(|id| id != child_id);
+    }
+}
+
+pub fn path_tree() -> RootedTree<String, PathNode> {
+    let mut tree = RootedTree::new();
+    tree.add_node(None, PathNode::new("/")).unwrap();
+    for (parent, path) in [
+        ("/", "/home"),
+        ("/home", "/home/alice"),
+        ("/home/alice", "/home/alice/Documents"),
+        ("/home", "/home/bob"),
+        ("/home/bob", "/home/bob/Documents"),
+        ("/", "/etc"),
+        ("/etc", "/etc/hosts"),
+    ] {
+        tree.add_node(Some(parent.to_string()), PathNode::new(path))
+            .unwrap();
+    }
+    tree
 }
@@ -0,0 +1,329 @@
+use crate::{Node, RootedTree};
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+
+impl<I: Eq + PartialEq + Clone + Hash, N: Node<I>> RootedTree<I, N> {
+    /// Iterate over the subtree of `id` in level-order, starting with `id` itself.
+    pub fn bfs_from(&self, id: &I) -> impl Iterator<Item = &N> + '_ {
+        let mut queue: VecDeque<&N> = self.get_node(id).into_iter().collect();
+        std::iter::from_fn(move || {
+            let node = queue.pop_front()?;
+            for child_id in node.child_ids_vec() {
+                if let Some(child) = self.child_nodes.get(&child_id) {
+                    queue.push_back(child);
+                }
+            }
+            Some(node)
+        })
+    }
+
+    /// Iterate over the ancestors of `id` present in the tree, nearest parent first.
+    pub fn ancestors(&self, id: &I) -> impl Iterator<Item = &N> + '_ {
+        let node = self.get_node(id);
+        std::iter::successors(node, move |node| {
+            node.parent_id()
+                .and_then(|parent_id| self.get_node(&parent_id))
+        })
+        .skip(1)
+    }
+
+    /// Iterate over the descendants of `id` in pre-order, `id` excluded.
+    pub fn descendants(&self, id: &I) -> impl Iterator<Item = &N> + '_ {
+        let mut stack: Vec<&N> = self
+            .get_node(id)
+            .map(|node| self.child_edges(node).map(|(_, child)| child).collect())
+            .unwrap_or_default();
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(self.child_edges(node).map(|(_, child)| child));
+            Some(node)
+        })
+    }
+
+    /// Ids of `id` and of the nodes reachable from it, in pre-order.
+    ///
+    /// Each id is visited once, so a cyclic `child_ids` graph terminates.
+    pub fn reachable_from(&self, id: &I) -> Vec<I> {
+        let mut out = vec![];
+        let mut visited = HashSet::new();
+        let mut stack: Vec<&N> = self.get_node(id).into_iter().collect();
+        while let Some(node) = stack.pop() {
+            if !visited.insert(node.id()) {
+                continue;
+            }
+            out.push(node.id());
+            stack.extend(self.child_edges(node).map(|(_, child)| child));
+        }
+        out
+    }
+
+    /// Iterate mutably over the root and every child node once, in no particular order.
+    ///
+    /// Meant to edit payloads: the ids and the parent and child ids must be left unchanged.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut N> + '_ {
+        self.root_node
+            .iter_mut()
+            .chain(self.child_nodes.values_mut())
+    }
+
+    /// Ids of the nodes in pre-order, root first, without recursion.
+    pub fn dfs_ids(&self) -> Vec<I> {
+        match &self.root_node {
+            Some(root_node) => self.reachable_from(&root_node.id()),
+            None => vec![],
+        }
+    }
+
+    /// Call `f(parent, child)` for every edge between two existing nodes, in depth-first order.
+    pub fn for_each_edge<F: FnMut(&N, &N)>(&self, mut f: F) {
+        let mut stack = vec![];
+        if let Some(root_node) = &self.root_node {
+            stack.extend(self.child_edges(root_node));
+        }
+        while let Some((parent, child)) = stack.pop() {
+            f(parent, child);
+            stack.extend(self.child_edges(child));
+        }
+    }
+
+    /// Ids of the nodes without child ids, in pre-order.
+    pub fn leaves(&self) -> Vec<I> {
+        self.root_node
+            .iter()
+            .flat_map(|root_node| {
+                std::iter::once(root_node).chain(self.descendants(&root_node.id()))
+            })
+            .filter(|node| node.child_ids_vec().is_empty())
+            .map(|node| node.id())
+            .collect()
+    }
+
+    /// Number of paths from the root down to a leaf, as many as `leaves`.
+    pub fn path_count(&self) -> usize {
+        self.root_node
+            .iter()
+            .flat_map(|root_node| {
+                std::iter::once(root_node).chain(self.descendants(&root_node.id()))
+            })
+            .filter(|node| node.child_ids_vec().is_empty())
+            .count()
+    }
+
+    /// Each leaf with the ids from the root down to the leaf, in depth-first order.
+    pub fn leaves_with_paths(&self) -> Vec<(I, Vec<I>)> {
+        let mut out = vec![];
+        let mut stack = vec![];
+        if let Some(root_node) = &self.root_node {
+            stack.push((root_node, vec![root_node.id()]));
+        }
+        while let Some((node, path)) = stack.pop() {
+            let child_ids = node.child_ids_vec();
+            if child_ids.is_empty() {
+                out.push((node.id(), path));
+                continue;
+            }
+            for child in child_ids
+                .iter()
+                .rev()
+                .filter_map(|child_id| self.child_nodes.get(child_id))
+            {
+                let mut child_path = path.clone();
+                child_path.push(child.id());
+                stack.push((child, child_path));
+            }
+        }
+        out
+    }
+
+    // Edges from `node` to its existing children, in reverse order for stack usage
+    fn child_edges<'a>(&'a self, node: &'a N) -> impl Iterator<Item = (&'a N, &'a N)> + 'a {
+        node.child_ids_vec()
+            .into_iter()
+            .rev()
+            .filter_map(move |child_id| self.child_nodes.get(&child_id))
+            .map(move |child| (node, child))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::*;
+
+    #[test]
+    fn bfs_from_child() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(9)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(3), DataNode::new(5)).unwrap();
+        tree.add_node(Some(4), DataNode::new(6)).unwrap();
+
+        let ids: Vec<i32> = tree.bfs_from(&2).map(|node| node.id()).collect();
+        assert_eq!(ids, vec![2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn bfs_from_missing_node() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+
+        assert_eq!(tree.bfs_from(&2).count(), 0);
+    }
+
+    #[test]
+    fn ancestors_and_descendants() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(2), DataNode::new(5)).unwrap();
+        tree.add_node(Some(4), DataNode::new(6)).unwrap();
+
+        let ids: Vec<i32> = tree.ancestors(&4).map(|node| node.id()).collect();
+        assert_eq!(ids, vec![2, 1]);
+        let ids: Vec<i32> = tree.descendants(&2).map(|node| node.id()).collect();
+        assert_eq!(ids, vec![4, 6, 5]);
+
+        assert_eq!(tree.ancestors(&1).count(), 0);
+        assert_eq!(tree.descendants(&6).count(), 0);
+        assert_eq!(tree.ancestors(&10).count(), 0);
+        assert_eq!(tree.descendants(&10).count(), 0);
+    }
+
+    #[test]
+    fn for_each_edge() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(3), DataNode::new(5)).unwrap();
+
+        let mut edges = vec![];
+        tree.for_each_edge(|parent, child| edges.push((parent.id(), child.id())));
+
+        assert_eq!(edges.len(), tree.len() - 1);
+        assert_eq!(edges, vec![(1, 2), (2, 4), (1, 3), (3, 5)]);
+    }
+
+    #[test]
+    fn reachable_from() {
+        let mut tree = RootedTree::new();
+        assert_eq!(tree.reachable_from(&1), Vec::<i32>::new());
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(1), DataNode::new(4)).unwrap();
+        assert_eq!(tree.reachable_from(&1), vec![1, 2, 3, 4]);
+        assert_eq!(tree.reachable_from(&2), vec![2, 3]);
+
+        // 3 points back to its parent
+        let mut node = DataNode::new(3);
+        node.set_parent_id(2);
+        node.add_child_id(2);
+        tree.set_child_node(node).unwrap();
+        assert_eq!(tree.reachable_from(&1), vec![1, 2, 3, 4]);
+        assert_eq!(tree.reachable_from(&3), vec![3, 2]);
+    }
+
+    #[test]
+    fn dfs_ids() {
+        let mut tree = RootedTree::new();
+        assert_eq!(tree.dfs_ids(), Vec::<i32>::new());
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+        tree.add_node(Some(1), DataNode::new(4)).unwrap();
+        assert_eq!(tree.dfs_ids(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut tree = RootedTree::new();
+        assert_eq!(tree.iter_mut().count(), 0);
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(2), DataNode::new(3)).unwrap();
+
+        for node in tree.iter_mut() {
+            node.value = node.id * 10;
+        }
+
+        assert_eq!(tree.iter_mut().count(), tree.len());
+        for id in [1, 2, 3] {
+            assert_eq!(tree.get_node(&id).unwrap().value, id * 10);
+        }
+    }
+
+    #[test]
+    fn leaves() {
+        let mut tree = RootedTree::new();
+        assert_eq!(tree.leaves(), Vec::<i32>::new());
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        assert_eq!(tree.leaves(), vec![1]);
+
+        for (parent_id, ids) in [
+            (1, [2, 3, 4]),
+            (2, [5, 6, 7]),
+            (6, [8, 9, 10]),
+            (4, [11, 12, 13]),
+            (10, [14, 15, 16]),
+        ] {
+            for id in ids {
+                tree.add_node(Some(parent_id), DataNode::new(id)).unwrap();
+            }
+        }
+        assert_eq!(tree.leaves(), vec![5, 8, 9, 14, 15, 16, 7, 3, 11, 12, 13]);
+    }
+
+    #[test]
+    fn leaves_partial() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        let mut node = DataNode::new(2);
+        node.add_child_id(4);
+        tree.add_node(Some(1), node).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+
+        assert_eq!(tree.leaves(), vec![3]);
+    }
+
+    #[test]
+    fn path_count() {
+        let mut tree = RootedTree::new();
+        assert_eq!(tree.path_count(), 0);
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        assert_eq!(tree.path_count(), 1);
+
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(2), DataNode::new(5)).unwrap();
+        let mut node = DataNode::new(6);
+        node.add_child_id(7);
+        tree.add_node(Some(3), node).unwrap();
+
+        assert_eq!(tree.path_count(), 2);
+        assert_eq!(tree.path_count(), tree.leaves().len());
+        assert_eq!(tree.path_count(), tree.leaves_with_paths().len());
+    }
+
+    #[test]
+    fn leaves_with_paths() {
+        let mut tree = RootedTree::new();
+        tree.add_node(None, DataNode::new(1)).unwrap();
+        tree.add_node(Some(1), DataNode::new(2)).unwrap();
+        tree.add_node(Some(1), DataNode::new(3)).unwrap();
+        tree.add_node(Some(2), DataNode::new(4)).unwrap();
+        tree.add_node(Some(2), DataNode::new(5)).unwrap();
+
+        assert_eq!(
+            tree.leaves_with_paths(),
+            vec![(4, vec![1, 2, 4]), (5, vec![1, 2, 5]), (3, vec![1, 3]),]
+        );
+    }
+}